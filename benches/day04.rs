@@ -0,0 +1,76 @@
+//! Runs day 4's word search on an owned `Grid<u8>` and on the zero-copy `ByteGrid`. The searches
+//! are the solution's own, included from `src/bin/04.rs`.
+//!
+//! Uses `data/inputs/04.txt` if present, otherwise tiles the example up to the size of a real input.
+use std::fs;
+
+use advent_of_code::grid::{ByteGrid, Grid};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+// the binary's `main` and tests are not used here.
+#[allow(dead_code, unused_imports)]
+#[path = "../src/bin/04.rs"]
+mod day04;
+
+fn read_input() -> String {
+    if let Ok(input) = fs::read_to_string("data/inputs/04.txt") {
+        if !input.trim().is_empty() {
+            return input;
+        }
+    }
+
+    let example = fs::read_to_string("data/examples/04.txt").expect("could not open example file");
+    let lines: Vec<&str> = example.lines().collect();
+
+    (0..14)
+        .flat_map(|_| lines.iter())
+        .map(|line| line.repeat(14) + "\n")
+        .collect()
+}
+
+fn parse_grid(input: &str) -> Grid<u8> {
+    Grid::parse_with_parser(0, input, |c| c as u8).unwrap()
+}
+
+fn bench_day04(c: &mut Criterion) {
+    let input = read_input();
+    let grid = parse_grid(&input);
+    let bytes = ByteGrid::parse(&input).unwrap();
+
+    assert_eq!(
+        day04::count_xmas(&grid, grid.find_all(&b'X')),
+        day04::count_xmas(&bytes, bytes.find_iter(b'X'))
+    );
+    assert_eq!(
+        day04::count_x_mas(&grid, grid.find_all(&b'A')),
+        day04::count_x_mas(&bytes, bytes.find_iter(b'A'))
+    );
+
+    let mut parse = c.benchmark_group("day04/parse");
+    parse.bench_function("Grid<u8>", |b| b.iter(|| parse_grid(black_box(&input))));
+    parse.bench_function("ByteGrid", |b| {
+        b.iter(|| ByteGrid::parse(black_box(&input)).unwrap())
+    });
+    parse.finish();
+
+    let mut part_one = c.benchmark_group("day04/part_one");
+    part_one.bench_function("Grid<u8>", |b| {
+        b.iter(|| day04::count_xmas(black_box(&grid), grid.find_all(&b'X')))
+    });
+    part_one.bench_function("ByteGrid", |b| {
+        b.iter(|| day04::count_xmas(black_box(&bytes), bytes.find_iter(b'X')))
+    });
+    part_one.finish();
+
+    let mut part_two = c.benchmark_group("day04/part_two");
+    part_two.bench_function("Grid<u8>", |b| {
+        b.iter(|| day04::count_x_mas(black_box(&grid), grid.find_all(&b'A')))
+    });
+    part_two.bench_function("ByteGrid", |b| {
+        b.iter(|| day04::count_x_mas(black_box(&bytes), bytes.find_iter(b'A')))
+    });
+    part_two.finish();
+}
+
+criterion_group!(benches, bench_day04);
+criterion_main!(benches);
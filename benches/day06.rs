@@ -0,0 +1,146 @@
+//! Runs day 6's guard walk against each grid backend, to compare their layouts in a hot loop. The
+//! walk is written once against the grid traits, and once more for `PaddedGrid`'s sentinel border.
+//!
+//! Uses `data/inputs/06.txt` if present, otherwise tiles the example up to the size of a real input.
+use std::fs;
+
+use advent_of_code::grid::{ArrayGrid, Grid, GridRead, GridWrite, PaddedGrid};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+const SIZE: usize = 130;
+
+#[derive(Clone, Copy, Default, PartialEq)]
+enum Cell {
+    #[default]
+    Open,
+    Visited,
+    Obstacle,
+    Outside,
+}
+
+static DIRECTIONS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+fn read_input() -> String {
+    if let Ok(input) = fs::read_to_string("data/inputs/06.txt") {
+        if input.lines().count() == SIZE {
+            return input;
+        }
+    }
+
+    let example = fs::read_to_string("data/examples/06.txt").expect("could not open example file");
+    let lines: Vec<&str> = example.lines().collect();
+    let tiled: String = (0..SIZE / lines.len())
+        .flat_map(|_| lines.iter())
+        .map(|line| line.repeat(SIZE / line.len()) + "\n")
+        .collect();
+
+    // only keep the first guard
+    let guard = tiled.find('^').unwrap();
+    tiled[..=guard].to_string() + &tiled[guard + 1..].replace('^', ".")
+}
+
+fn parse_cell(c: char) -> Cell {
+    match c {
+        '#' => Cell::Obstacle,
+        _ => Cell::Open,
+    }
+}
+
+fn find_guard(input: &str) -> (usize, usize) {
+    let offset = input.find('^').unwrap();
+    let width = input.find('\n').unwrap() + 1;
+    (offset % width, offset / width)
+}
+
+/// Walks the guard until they leave the grid, counting the distinct cells visited.
+fn walk<G: GridWrite<Cell>>(grid: &mut G, start: (usize, usize)) -> usize {
+    let (mut pos, mut direction) = (start, 0);
+    let mut visited = 0;
+
+    loop {
+        if grid.get(pos.0, pos.1) != Some(&Cell::Visited) {
+            grid.set(pos.0, pos.1, Cell::Visited);
+            visited += 1;
+        }
+
+        match grid.step(pos, DIRECTIONS[direction]) {
+            None => return visited,
+            Some(Cell::Obstacle) => direction = (direction + 1) % 4,
+            Some(_) => {
+                let (dx, dy) = DIRECTIONS[direction];
+                pos = (pos.0.wrapping_add_signed(dx), pos.1.wrapping_add_signed(dy));
+            }
+        }
+    }
+}
+
+/// The same walk on a padded grid, which checks for its border instead of the grid bounds.
+fn walk_padded(grid: &mut PaddedGrid<Cell>, start: (usize, usize)) -> usize {
+    let (mut pos, mut direction) = (start, 0);
+    let mut visited = 0;
+
+    loop {
+        if grid.get(pos.0, pos.1) != Some(&Cell::Visited) {
+            grid.set(pos.0, pos.1, Cell::Visited);
+            visited += 1;
+        }
+
+        match grid.step_unchecked(pos, DIRECTIONS[direction]) {
+            Cell::Outside => return visited,
+            Cell::Obstacle => direction = (direction + 1) % 4,
+            _ => {
+                let (dx, dy) = DIRECTIONS[direction];
+                pos = (pos.0.wrapping_add_signed(dx), pos.1.wrapping_add_signed(dy));
+            }
+        }
+    }
+}
+
+fn bench_day06(c: &mut Criterion) {
+    let input = read_input();
+    let start = find_guard(&input);
+
+    let grid = Grid::parse_with_parser(Cell::Open, &input, parse_cell).unwrap();
+    let array =
+        ArrayGrid::<Cell, SIZE, SIZE>::parse_with_parser(Cell::Open, &input, parse_cell).unwrap();
+    let padded = PaddedGrid::from_grid(&grid, Cell::Outside);
+
+    let expected = walk(&mut grid.clone(), start);
+    assert_eq!(walk(&mut array.clone(), start), expected);
+    assert_eq!(walk(&mut padded.clone(), start), expected);
+    assert_eq!(walk_padded(&mut padded.clone(), start), expected);
+
+    let mut group = c.benchmark_group("day06/walk");
+    group.bench_function("Grid", |b| {
+        b.iter_batched_ref(
+            || grid.clone(),
+            |grid| walk(grid, black_box(start)),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("ArrayGrid", |b| {
+        b.iter_batched_ref(
+            || array.clone(),
+            |grid| walk(grid, black_box(start)),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("PaddedGrid", |b| {
+        b.iter_batched_ref(
+            || padded.clone(),
+            |grid| walk(grid, black_box(start)),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("PaddedGrid (sentinel)", |b| {
+        b.iter_batched_ref(
+            || padded.clone(),
+            |grid| walk_padded(grid, black_box(start)),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_day06);
+criterion_main!(benches);
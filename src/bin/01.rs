@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(1, Some(1110981), Some(24869388));
+advent_of_code::solution!(1, parse = parse_list, Some(1110981), Some(24869388));
 
 fn parse_list(input: &str) -> (Vec<u32>, Vec<u32>) {
     input
@@ -17,8 +17,8 @@ fn parse_list(input: &str) -> (Vec<u32>, Vec<u32>) {
         .unzip()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (mut l1, mut l2) = parse_list(input);
+pub fn part_one((l1, l2): &(Vec<u32>, Vec<u32>)) -> Option<u32> {
+    let (mut l1, mut l2) = (l1.clone(), l2.clone());
 
     l1.sort();
     l2.sort();
@@ -30,9 +30,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         .into()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (l1, l2) = parse_list(input);
-
+pub fn part_two((l1, l2): &(Vec<u32>, Vec<u32>)) -> Option<u32> {
     let mut map = HashMap::new();
     for item in l2.iter() {
         map.entry(item).and_modify(|e| *e += 1).or_insert(1);
@@ -50,13 +48,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse_list(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&input);
         assert_eq!(result, Some(11));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse_list(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&input);
         assert_eq!(result, Some(31));
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(2, Some(559), Some(601));
+advent_of_code::solution!(2, parse = parse, chain, Some(559), Some(601));
 
 fn parse(input: &str) -> Vec<Vec<u32>> {
     input
@@ -42,38 +42,30 @@ fn valid_report(report: &[u32]) -> bool {
         .is_ok()
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let reports = parse(input);
-
-    reports
+// part one hands on the invalid reports, which are the only ones part two needs to fix
+pub fn part_one(reports: &[Vec<u32>]) -> (Option<usize>, Vec<&[u32]>) {
+    let (valid, invalid): (Vec<&[u32]>, Vec<&[u32]>) = reports
         .iter()
-        .filter(|report| valid_report(report))
-        .count()
-        .into()
-}
-
-pub fn part_two(input: &str) -> Option<usize> {
-    let reports = parse(input);
+        .map(Vec::as_slice)
+        .partition(|report| valid_report(report));
 
-    let mut count = 0;
-    count += reports.iter().filter(|report| valid_report(report)).count();
-    assert_eq!(count, part_one(input)?);
-
-    let invalid_reports = reports.iter().filter(|report| !valid_report(report));
+    (Some(valid.len()), invalid)
+}
 
-    for report in invalid_reports {
-        for i in 0..report.len() {
-            let mut new = report.clone()[..i].to_vec();
-            new.extend(&report[i + 1..]);
+pub fn part_two(_: &[Vec<u32>], valid: Option<&usize>, invalid: &[&[u32]]) -> Option<usize> {
+    let fixed = invalid
+        .iter()
+        .filter(|report| {
+            (0..report.len()).any(|i| {
+                let mut new = report[..i].to_vec();
+                new.extend(&report[i + 1..]);
 
-            if valid_report(&new) {
-                count += 1;
-                break;
-            }
-        }
-    }
+                valid_report(&new)
+            })
+        })
+        .count();
 
-    Some(count)
+    Some(valid? + fixed)
 }
 
 #[cfg(test)]
@@ -82,13 +74,16 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let (result, _) = part_one(&input);
         assert_eq!(result, Some(2));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let (one, invalid) = part_one(&input);
+        let result = part_two(&input, one.as_ref(), &invalid);
         assert_eq!(result, Some(4));
     }
 }
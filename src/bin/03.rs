@@ -85,7 +85,7 @@ fn solve(input: &str, conditionals: bool) -> Option<u32> {
             }
             _ => {
                 if token.is_some() {
-                    //println!("Unexpected token: {} in state {:?}", c, token);
+                    advent_of_code::aoc_trace!("Unexpected token: {} in state {:?}", c, token);
                 }
                 token = None;
                 left = None;
@@ -1,103 +1,72 @@
-use advent_of_code::grid::{Grid, NeighbourTypes};
+use advent_of_code::grid::{ByteGrid, GridRead};
 
 advent_of_code::solution!(4, Some(2514), Some(1888));
 
-static SEARCH: &str = "XMAS";
+static SEARCH: &[u8] = b"XMAS";
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let grid = Grid::<char>::parse(input).expect("parsing grid");
-
-    let mut xmas = 0;
-
-    let mut candidates = Vec::new();
-    let first = SEARCH.chars().next().unwrap();
-
-    for (pos, value) in grid.iter() {
-        if value == first {
-            candidates.push(pos);
-        }
-    }
-
-    while let Some(pos) = candidates.pop() {
-        'neighbour: for (npos, nval) in
-            grid.neighbours_iter(&pos, NeighbourTypes::BasisSet | NeighbourTypes::Diagonals)
-        {
-            if *nval != SEARCH.chars().nth(1).unwrap() {
-                continue;
-            }
-
-            let (dx, dy) = (
-                npos.0 as isize - pos.0 as isize,
-                npos.1 as isize - pos.1 as isize,
-            );
+static DIRECTIONS: [(isize, isize); 8] = [
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (-1, 1),
+    (1, -1),
+    (1, 1),
+];
 
-            for i in 2..SEARCH.len() {
-                let (nx, ny) = (
-                    pos.0 as isize + (dx * i as isize),
-                    pos.1 as isize + (dy * i as isize),
-                );
-
-                if nx < 0 || ny < 0 || nx >= grid.width as isize || ny >= grid.height as isize {
-                    continue 'neighbour;
-                }
-
-                let v = grid.get(nx.try_into().unwrap(), ny.try_into().unwrap());
-                if v.is_none() || *v.unwrap() != SEARCH.chars().nth(i).unwrap() {
-                    continue 'neighbour;
-                }
-            }
-
-            xmas += 1;
-        }
-    }
+pub fn part_one(input: &str) -> Option<u32> {
+    let grid = ByteGrid::parse(input).expect("parsing grid");
 
-    Some(xmas)
+    Some(count_xmas(&grid, grid.find_iter(SEARCH[0])))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let grid = Grid::<char>::parse(input).expect("parsing grid");
-
-    let mut mas = 0;
+    let grid = ByteGrid::parse(input).expect("parsing grid");
 
-    let mut candidates = Vec::new();
-
-    for (pos, value) in grid.iter() {
-        if value == 'A' {
-            candidates.push(pos);
-        }
-    }
-
-    'candidate: while let Some((x, y)) = candidates.pop() {
-        for diagonal in [1, -1] {
-            let up = (x as isize + diagonal, y as isize - 1);
-            let down = (x as isize - diagonal, y as isize + 1);
-
-            if up.0 < 0 || up.1 < 0 || up.0 >= grid.width as isize || up.1 >= grid.height as isize {
-                continue 'candidate;
-            }
-            if down.0 < 0
-                || down.1 < 0
-                || down.0 >= grid.width as isize
-                || down.1 >= grid.height as isize
-            {
-                continue 'candidate;
-            }
-
-            let search = match grid.get(up.0.try_into().unwrap(), up.1.try_into().unwrap()) {
-                Some('M') => Some('S'),
-                Some('S') => Some('M'),
-                _ => continue 'candidate,
-            };
-
-            if grid.get(down.0.try_into().unwrap(), down.1.try_into().unwrap()) != search.as_ref() {
-                continue 'candidate;
-            }
-        }
+    Some(count_x_mas(&grid, grid.find_iter(b'A')))
+}
 
-        mas += 1;
-    }
+// both searches take any grid backend, so that benches/day04.rs can compare them on this code.
+
+/// Counts the words spelled in any direction from the `X`s at `candidates`.
+pub fn count_xmas<G: GridRead<u8>>(
+    grid: &G,
+    candidates: impl Iterator<Item = (usize, usize)>,
+) -> u32 {
+    candidates
+        .map(|pos| {
+            DIRECTIONS
+                .iter()
+                .filter(|&&(dx, dy)| {
+                    (1..SEARCH.len()).all(|i| {
+                        let offset = (dx * i as isize, dy * i as isize);
+                        grid.step(pos, offset) == Some(&SEARCH[i])
+                    })
+                })
+                .count() as u32
+        })
+        .sum()
+}
 
-    Some(mas)
+/// Counts the `A`s at `candidates` that cross two diagonal `MAS`.
+pub fn count_x_mas<G: GridRead<u8>>(
+    grid: &G,
+    candidates: impl Iterator<Item = (usize, usize)>,
+) -> u32 {
+    candidates
+        .filter(|&pos| {
+            [1, -1].into_iter().all(|diagonal| {
+                let up = grid.step(pos, (diagonal, -1));
+                let down = grid.step(pos, (-diagonal, 1));
+
+                matches!(
+                    (up, down),
+                    (Some(b'M'), Some(b'S')) | (Some(b'S'), Some(b'M'))
+                )
+            })
+        })
+        .count() as u32
 }
 
 #[cfg(test)]
@@ -115,4 +84,11 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(9));
     }
+
+    #[test]
+    fn test_part_one_alloc_budget() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let result = advent_of_code::assert_alloc_budget!(part_one(&input), 4096);
+        assert_eq!(result, Some(18));
+    }
 }
@@ -1,16 +1,8 @@
-use std::{
-    cmp,
-    collections::HashSet,
-};
+use std::{cmp, collections::HashSet};
 
-advent_of_code::solution!(5, Some(5452), Some(4598));
+advent_of_code::solution!(5, parse = parse, Some(5452), Some(4598));
 
-fn parse(
-    input: &str,
-) -> (
-    HashSet<(usize, usize)>,
-    impl Iterator<Item = Vec<usize>> + '_,
-) {
+fn parse(input: &str) -> (HashSet<(usize, usize)>, Vec<Vec<usize>>) {
     let (rules, pages) = input.split_once("\n\n").unwrap();
 
     let rules: HashSet<(usize, usize)> = rules
@@ -23,7 +15,8 @@ fn parse(
 
     let pages = pages
         .lines()
-        .map(|line| line.split(',').map(|page| page.parse().unwrap()).collect());
+        .map(|line| line.split(',').map(|page| page.parse().unwrap()).collect())
+        .collect();
 
     (rules, pages)
 }
@@ -38,12 +31,11 @@ fn compare_rules(rules: &HashSet<(usize, usize)>, a: &usize, b: &usize) -> cmp::
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let (rules, manuals) = parse(input);
-
+pub fn part_one((rules, manuals): &(HashSet<(usize, usize)>, Vec<Vec<usize>>)) -> Option<usize> {
     manuals
+        .iter()
         .filter_map(|manual| {
-            if manual.is_sorted_by(|a, b| compare_rules(&rules, a, b).is_le()) {
+            if manual.is_sorted_by(|a, b| compare_rules(rules, a, b).is_le()) {
                 Some(manual[manual.len() / 2])
             } else {
                 None
@@ -53,14 +45,13 @@ pub fn part_one(input: &str) -> Option<usize> {
         .into()
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let (rules, manuals) = parse(input);
-
+pub fn part_two((rules, manuals): &(HashSet<(usize, usize)>, Vec<Vec<usize>>)) -> Option<usize> {
     manuals
-        .into_iter()
+        .iter()
+        .cloned()
         .filter_map(|mut manual| {
-            if !manual.is_sorted_by(|a, b| compare_rules(&rules, a, b).is_le()) {
-                manual.sort_unstable_by(|a, b| compare_rules(&rules, a, b));
+            if !manual.is_sorted_by(|a, b| compare_rules(rules, a, b).is_le()) {
+                manual.sort_unstable_by(|a, b| compare_rules(rules, a, b));
                 Some(manual[manual.len() / 2])
             } else {
                 None
@@ -88,13 +79,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&input);
         assert_eq!(result, Some(143));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&input);
         assert_eq!(result, Some(123));
     }
 }
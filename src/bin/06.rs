@@ -1,46 +1,34 @@
 use std::collections::HashSet;
 use std::iter::Iterator;
 
-advent_of_code::solution!(6, Some(5269), Some(1957));
+use advent_of_code::grid::Grid;
+use advent_of_code::viz;
+
+advent_of_code::solution!(6, parse = parse, Some(5269), Some(1957));
 
 #[derive(Clone)]
-struct Guard {
+pub struct Guard {
     position: (usize, usize),
     direction: usize, // 0 = north, 1 = east, 2 = south, 3 = west
 }
 
 fn parse(input: &str) -> ((usize, usize), Guard, HashSet<(usize, usize)>) {
-    let mut guard = Guard {
-        position: (0, 0),
+    let grid = Grid::<char>::parse(input).expect("parsing grid");
+
+    let guard = Guard {
+        position: grid.find(&'^').expect("finding guard"),
         direction: 0, // guard always starts facing north?
     };
 
-    let mut obstacles = HashSet::new();
-    let (mut width, mut height) = (0, 0);
-
-    for (y, line) in input.lines().enumerate() {
-        height = y + 1;
-        width = line.len();
-
-        for (x, c) in line.chars().enumerate() {
-            match c {
-                '#' => {
-                    obstacles.insert((x, y));
-                }
-                '^' => {
-                    guard.position = (x, y);
-                }
-                _ => {}
-            }
-        }
-    }
+    let obstacles = grid.find_all(&'#').collect();
 
-    ((width, height), guard, obstacles)
+    ((grid.width(), grid.height()), guard, obstacles)
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let (dims, guard, obstacles) = parse(input);
-    guard_path(dims, &guard, &obstacles).len().into()
+pub fn part_one(
+    (dims, guard, obstacles): &((usize, usize), Guard, HashSet<(usize, usize)>),
+) -> Option<usize> {
+    guard_path(*dims, guard, obstacles).len().into()
 }
 
 fn guard_path(
@@ -59,7 +47,17 @@ fn guard_path(
             TourResult::Stepped => {
                 path.insert(next);
             }
-            _ => {}
+            TourResult::Turned => {
+                if viz::enabled() {
+                    viz::frame(render(
+                        dims,
+                        &local_guard,
+                        obstacles,
+                        |coord| path.contains(coord),
+                        None,
+                    ));
+                }
+            }
         }
 
         local_guard.position = next;
@@ -69,8 +67,10 @@ fn guard_path(
     path
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (dims, mut guard, obstacles) = parse(input);
+pub fn part_two(
+    (dims, guard, obstacles): &((usize, usize), Guard, HashSet<(usize, usize)>),
+) -> Option<u32> {
+    let (dims, mut guard) = (*dims, guard.clone());
     let mut visited = HashSet::new();
     let mut step = 0;
     let mut loops = 0;
@@ -146,6 +146,14 @@ pub fn part_two(input: &str) -> Option<u32> {
                     )
                 {
                     loops += 1;
+
+                    if viz::enabled() {
+                        let on_path = |coord: &(usize, usize)| {
+                            (0..4).any(|dir| visited.contains(&(*coord, dir, 0)))
+                        };
+                        viz::frame(render(dims, &guard, obstacles, on_path, Some(npos)));
+                        viz::log(format_args!("{loops} obstacles make the guard loop"));
+                    }
                 }
 
                 // the guard now advances to the position they would have done if the new obstacle
@@ -169,6 +177,32 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(loops)
 }
 
+/// Draws the guard, their path so far and the obstacles, with an extra obstacle drawn as `O`.
+fn render(
+    dims: (usize, usize),
+    guard: &Guard,
+    obstacles: &HashSet<(usize, usize)>,
+    path: impl Fn(&(usize, usize)) -> bool,
+    extra: Option<(usize, usize)>,
+) -> String {
+    let mut out = String::new();
+
+    for y in 0..dims.1 {
+        for x in 0..dims.0 {
+            out.push(match (x, y) {
+                coord if coord == guard.position => ['^', '>', 'v', '<'][guard.direction],
+                coord if Some(coord) == extra => 'O',
+                coord if obstacles.contains(&coord) => '#',
+                coord if path(&coord) => 'X',
+                _ => '.',
+            });
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
 enum TourResult {
     OutOfBounds,
     Stepped,
@@ -189,14 +223,14 @@ fn guard_tour<O: Fn(&(usize, usize)) -> bool>(
 
         match result {
             TourResult::OutOfBounds => return false,
-            TourResult::Stepped => {
-                // We can always consider any step on the base path at step 0 without any extra
-                // obstacles. Surprisingly this seems to save ~200ms, presumably because we can
-                // terminate many walks early on the base path rather than building up the path
-                // again in the visit map on the current step.
-                if visited.contains(&(next, ndir, 0)) || visited.contains(&(next, ndir, step)) {
-                    return true;
-                }
+            // We can always consider any step on the base path at step 0 without any extra
+            // obstacles. Surprisingly this seems to save ~200ms, presumably because we can
+            // terminate many walks early on the base path rather than building up the path
+            // again in the visit map on the current step.
+            TourResult::Stepped
+                if visited.contains(&(next, ndir, 0)) || visited.contains(&(next, ndir, step)) =>
+            {
+                return true;
             }
             _ => {}
         }
@@ -245,13 +279,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&input);
         assert_eq!(result, Some(41));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&input);
         assert_eq!(result, Some(6));
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(7, Some(4555081946288), Some(227921760109726));
+advent_of_code::solution!(7, parse = parse, Some(4555081946288), Some(227921760109726));
 
 use itertools::Itertools;
 
@@ -9,7 +9,7 @@ enum Op {
     Concatenate,
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+fn parse(input: &str) -> Vec<(u64, Vec<u64>)> {
     input
         .lines()
         .map(|line| {
@@ -21,22 +21,25 @@ pub fn part_one(input: &str) -> Option<u64> {
 
             (solution.parse().unwrap(), inputs)
         })
-        .filter_map(|(soln, ins)| {
+        .collect()
+}
+
+pub fn part_one(equations: &[(u64, Vec<u64>)]) -> Option<u64> {
+    equations
+        .iter()
+        .filter_map(|&(soln, ref ins)| {
             (0..ins.len() - 1)
                 .map(|_| [Op::Add, Op::Multiply].iter())
                 .multi_cartesian_product()
                 .any(|ops| {
-                    let mut i = 1;
                     let mut result = ins[0];
 
-                    for op in ops {
+                    for (i, op) in (1..).zip(ops) {
                         match op {
                             Op::Add => result += ins[i],
                             Op::Multiply => result *= ins[i],
                             _ => panic!("Invalid operation"),
                         }
-
-                        i += 1;
                     }
 
                     result == soln
@@ -47,27 +50,20 @@ pub fn part_one(input: &str) -> Option<u64> {
         .into()
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    input
-        .lines()
-        .map(|line| {
-            let (solution, inputs) = line.split_once(": ").unwrap();
-            let inputs = inputs
-                .split(" ")
-                .map(|i| i.parse().unwrap())
-                .collect::<Vec<u64>>();
+pub fn part_two(equations: &[(u64, Vec<u64>)]) -> Option<u64> {
+    let bar = advent_of_code::progress::bar(equations.len());
 
-            (solution.parse().unwrap(), inputs)
-        })
-        .filter_map(|(soln, ins)| {
+    equations
+        .iter()
+        .inspect(|_| bar.tick())
+        .filter_map(|&(soln, ref ins)| {
             (0..ins.len() - 1)
                 .map(|_| [Op::Add, Op::Multiply, Op::Concatenate].iter())
                 .multi_cartesian_product()
                 .any(|ops| {
-                    let mut i = 1;
                     let mut result = ins[0];
 
-                    for op in ops {
+                    for (i, op) in (1..).zip(ops) {
                         match op {
                             Op::Add => result += ins[i],
                             Op::Multiply => result *= ins[i],
@@ -86,8 +82,6 @@ pub fn part_two(input: &str) -> Option<u64> {
                                     .unwrap();
                             }
                         }
-
-                        i += 1;
                     }
 
                     result == soln
@@ -104,13 +98,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&input);
         assert_eq!(result, Some(3749));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&input);
         assert_eq!(result, Some(11387));
     }
 }
@@ -0,0 +1,235 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use crate::grid::Grid;
+
+/// Describes how a grid cell may be walked through when contracting a maze.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Passage {
+    Blocked,
+    Open,
+    // a one-way cell may only be left in the given (dx, dy) direction
+    OneWay((isize, isize)),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Edge {
+    pub to: usize,
+    pub weight: usize,
+}
+
+/// A weighted graph of the junctions in a corridor maze. Nodes are the start, the end and every
+/// open cell with more than two open neighbours; edges are the corridors between them, weighted by
+/// the number of steps taken along the corridor.
+pub struct JunctionGraph {
+    pub nodes: Vec<(usize, usize)>,
+    pub edges: Vec<Vec<Edge>>,
+    /// The node index of each node position.
+    index: HashMap<(usize, usize), usize>,
+}
+
+impl JunctionGraph {
+    pub fn contract<T, P>(
+        grid: &Grid<T>,
+        start: (usize, usize),
+        end: (usize, usize),
+        passage: P,
+    ) -> Self
+    where
+        T: Clone,
+        P: Fn(&T) -> Passage,
+    {
+        let passage_at = |(x, y): (usize, usize)| passage(grid.get(x, y).unwrap());
+
+        let mut nodes = vec![start, end];
+
+        for (pos, value) in grid.iter() {
+            if pos == start || pos == end || passage(&value) == Passage::Blocked {
+                continue;
+            }
+
+            let exits = grid
+                .neighbours_iter_basis(&pos)
+                .filter(|(_, nval)| passage(nval) != Passage::Blocked)
+                .count();
+
+            if exits > 2 {
+                nodes.push(pos);
+            }
+        }
+
+        let index: HashMap<(usize, usize), usize> =
+            nodes.iter().enumerate().map(|(i, &pos)| (pos, i)).collect();
+
+        let mut edges = vec![Vec::new(); nodes.len()];
+
+        for (from, &node) in nodes.iter().enumerate() {
+            for (first, _) in grid.neighbours_iter_basis(&node) {
+                if !can_step(node, first, passage_at) {
+                    continue;
+                }
+
+                // walk the corridor until it either dead-ends or reaches another node
+                let (mut prev, mut current, mut weight) = (node, first, 1);
+
+                let to = loop {
+                    if let Some(&to) = index.get(&current) {
+                        break Some(to);
+                    }
+
+                    let next = grid
+                        .neighbours_iter_basis(&current)
+                        .map(|(npos, _)| npos)
+                        .find(|&npos| npos != prev && can_step(current, npos, passage_at));
+
+                    match next {
+                        Some(next) => {
+                            (prev, current) = (current, next);
+                            weight += 1;
+                        }
+                        None => break None,
+                    }
+                };
+
+                if let Some(to) = to.filter(|&to| to != from) {
+                    edges[from].push(Edge { to, weight });
+                }
+            }
+        }
+
+        Self {
+            nodes,
+            edges,
+            index,
+        }
+    }
+
+    /// Returns the node index of a grid position, if the position is a node in the graph.
+    pub fn node(&self, pos: (usize, usize)) -> Option<usize> {
+        self.index.get(&pos).copied()
+    }
+
+    /// Length of the longest simple path between two nodes, found by an exhaustive DFS over the
+    /// graph. The visited set is a bitset, so the graph may have at most 128 nodes.
+    pub fn longest_path(&self, from: usize, to: usize) -> Option<usize> {
+        assert!(self.nodes.len() <= 128, "too many nodes for longest path");
+
+        self.longest_path_from(from, to, 1 << from)
+    }
+
+    fn longest_path_from(&self, current: usize, to: usize, visited: u128) -> Option<usize> {
+        if current == to {
+            return Some(0);
+        }
+
+        self.edges[current]
+            .iter()
+            .filter(|edge| visited & (1 << edge.to) == 0)
+            .filter_map(|edge| {
+                self.longest_path_from(edge.to, to, visited | (1 << edge.to))
+                    .map(|length| length + edge.weight)
+            })
+            .max()
+    }
+
+    /// Length of the shortest path between two nodes, using Dijkstra's algorithm.
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<usize> {
+        let mut distances = vec![usize::MAX; self.nodes.len()];
+        let mut queue = BinaryHeap::new();
+
+        distances[from] = 0;
+        queue.push(Reverse((0, from)));
+
+        while let Some(Reverse((distance, node))) = queue.pop() {
+            if node == to {
+                return Some(distance);
+            }
+
+            if distance > distances[node] {
+                continue;
+            }
+
+            for edge in &self.edges[node] {
+                let next = distance + edge.weight;
+
+                if next < distances[edge.to] {
+                    distances[edge.to] = next;
+                    queue.push(Reverse((next, edge.to)));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+fn can_step<F: Fn((usize, usize)) -> Passage>(
+    from: (usize, usize),
+    to: (usize, usize),
+    passage_at: F,
+) -> bool {
+    let direction = (
+        to.0 as isize - from.0 as isize,
+        to.1 as isize - from.1 as isize,
+    );
+
+    if let Passage::OneWay(exit) = passage_at(from) {
+        if exit != direction {
+            return false;
+        }
+    }
+
+    match passage_at(to) {
+        Passage::Blocked => false,
+        Passage::Open => true,
+        // stepping onto a one-way cell against its direction would leave no way out
+        Passage::OneWay((dx, dy)) => (dx, dy) != (-direction.0, -direction.1),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{JunctionGraph, Passage};
+    use crate::grid::Grid;
+
+    static MAZE: &str = "\
+#.#####
+#.....#
+#.###.#
+#.....#
+###.#.#
+###...#
+#####.#";
+
+    fn passage(c: &char) -> Passage {
+        match c {
+            '#' => Passage::Blocked,
+            '>' => Passage::OneWay((1, 0)),
+            'v' => Passage::OneWay((0, 1)),
+            _ => Passage::Open,
+        }
+    }
+
+    #[test]
+    fn contracts_corridors_to_junctions() {
+        let grid = Grid::<char>::parse(MAZE).unwrap();
+        let graph = JunctionGraph::contract(&grid, (1, 0), (5, 6), passage);
+
+        // start, end and the junctions at (1, 1), (3, 3), (5, 3) and (5, 5)
+        assert_eq!(graph.nodes.len(), 6);
+        assert_eq!(graph.shortest_path(0, 1), Some(10));
+        assert_eq!(graph.longest_path(0, 1), Some(14));
+    }
+
+    #[test]
+    fn respects_one_way_cells() {
+        let grid = Grid::<char>::parse(MAZE.replacen("#.###.#", "#v###.#", 1)).unwrap();
+        let graph = JunctionGraph::contract(&grid, (1, 0), (5, 6), passage);
+
+        let (top, middle) = (graph.node((1, 1)).unwrap(), graph.node((3, 3)).unwrap());
+        assert!(graph.edges[top].iter().any(|edge| edge.to == middle));
+        assert!(!graph.edges[middle].iter().any(|edge| edge.to == top));
+    }
+}
@@ -0,0 +1,121 @@
+use super::{GridRead, GridWrite};
+
+/// A fixed-size grid whose dimensions are known at compile time, so that it can live on the stack.
+#[derive(Clone)]
+pub struct ArrayGrid<T, const W: usize, const H: usize> {
+    data: [[T; W]; H],
+}
+
+impl<T, const W: usize, const H: usize> ArrayGrid<T, W, H>
+where
+    T: Clone,
+{
+    pub fn new(default: T) -> Self {
+        Self {
+            data: std::array::from_fn(|_| std::array::from_fn(|_| default.clone())),
+        }
+    }
+
+    // parse assumes the input is exactly W characters wide and H lines high
+    pub fn parse_with_parser<Parser: Fn(char) -> T>(
+        default: T,
+        input: &str,
+        parser: Parser,
+    ) -> Result<Self, String> {
+        let mut grid = Self::new(default);
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            if y >= H || line.len() != W {
+                return Err(format!("grid is not {W}x{H}"));
+            }
+
+            for (x, character) in line.chars().enumerate() {
+                grid.data[y][x] = parser(character);
+            }
+
+            height += 1;
+        }
+
+        if height != H {
+            return Err(format!("grid is not {W}x{H}"));
+        }
+
+        Ok(grid)
+    }
+}
+
+impl<T, const W: usize, const H: usize> GridRead<T> for ArrayGrid<T, W, H> {
+    fn width(&self) -> usize {
+        W
+    }
+
+    fn height(&self) -> usize {
+        H
+    }
+
+    fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.data.get(y)?.get(x)
+    }
+}
+
+impl<T, const W: usize, const H: usize> GridWrite<T> for ArrayGrid<T, W, H> {
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.data.get_mut(y)?.get_mut(x)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::ArrayGrid;
+    use crate::grid::{GridRead, GridWrite};
+
+    #[test]
+    fn gets_and_sets_within_bounds() {
+        let mut grid = ArrayGrid::<u8, 3, 2>::new(0);
+
+        grid.set(2, 1, 7);
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(2, 1), Some(&7));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+        assert_eq!(grid.get_mut(3, 1), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn panics_setting_out_of_bounds() {
+        ArrayGrid::<u8, 3, 2>::new(0).set(0, 2, 1);
+    }
+
+    #[test]
+    fn steps_to_neighbours() {
+        let grid = ArrayGrid::<char, 3, 3>::parse_with_parser('.', "abc\ndef\nghi", |c| c).unwrap();
+        let neighbours = |pos| {
+            [
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ]
+            .into_iter()
+            .filter_map(|offset| grid.step(pos, offset).copied())
+            .collect::<String>()
+        };
+
+        assert_eq!(neighbours((1, 1)), "abcdfghi");
+        assert_eq!(neighbours((0, 0)), "bde");
+        assert_eq!(neighbours((2, 2)), "efh");
+    }
+
+    #[test]
+    fn rejects_mismatched_dimensions() {
+        assert!(ArrayGrid::<char, 3, 2>::parse_with_parser('.', "abc\nde", |c| c).is_err());
+        assert!(ArrayGrid::<char, 3, 2>::parse_with_parser('.', "abc", |c| c).is_err());
+        assert!(ArrayGrid::<char, 3, 2>::parse_with_parser('.', "abc\ndef\nghi", |c| c).is_err());
+    }
+}
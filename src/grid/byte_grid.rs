@@ -0,0 +1,225 @@
+use std::fmt::Display;
+
+use super::{next_neighbour, GridRead, NeighbourTypes, NeighbourWithOffset, Neighbourhood};
+
+/// A read-only grid over the bytes of a puzzle input, without copying it. Rows are addressed with
+/// a stride of the line width plus the trailing newline, so coordinates map directly to offsets
+/// into the input.
+#[derive(Clone, Copy)]
+pub struct ByteGrid<'a> {
+    data: &'a [u8],
+    width: usize,
+    height: usize,
+    stride: usize,
+}
+
+impl<'a> ByteGrid<'a> {
+    // parse assumes lines are terminated by `\n`, with an optional newline after the last line
+    pub fn parse(input: &'a str) -> Result<Self, String> {
+        let data = input.as_bytes();
+
+        let width = memchr::memchr(b'\n', data).unwrap_or(data.len());
+        if width == 0 {
+            return Err("grid is empty".to_string());
+        }
+
+        // account for the newline missing from the last line, if there is one
+        let len = if data.ends_with(b"\n") {
+            data.len()
+        } else {
+            data.len() + 1
+        };
+
+        let stride = width + 1;
+        if len % stride != 0 {
+            return Err("grid is not rectangular".to_string());
+        }
+
+        let height = len / stride;
+
+        // each row must end in a newline and hold none before it, or rows of differing widths
+        // could line up with the stride by chance
+        let rectangular = (0..height).all(|y| {
+            let row = &data[y * stride..y * stride + width];
+            let terminated = match data.get(y * stride + width) {
+                Some(&c) => c == b'\n',
+                None => y == height - 1,
+            };
+
+            terminated && memchr::memchr(b'\n', row).is_none()
+        });
+
+        if !rectangular {
+            return Err("grid is not rectangular".to_string());
+        }
+
+        Ok(Self {
+            data,
+            width,
+            height,
+            stride,
+        })
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&'a u8> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        self.data.get(self.offset(x, y))
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Byte offset of `(x, y)` into the input.
+    pub fn offset(&self, x: usize, y: usize) -> usize {
+        y * self.stride + x
+    }
+
+    /// Grid position of a byte offset into the input, or `None` if the offset is out of range or
+    /// points at a newline.
+    pub fn position(&self, offset: usize) -> Option<(usize, usize)> {
+        let (x, y) = (offset % self.stride, offset / self.stride);
+
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some((x, y))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), u8)> + 'a {
+        let grid = *self;
+
+        (0..self.height)
+            .flat_map(move |y| (0..grid.width).map(move |x| ((x, y), grid.data[grid.offset(x, y)])))
+    }
+
+    /// Finds the first occurrence of `byte` in row-major order.
+    pub fn find(&self, byte: u8) -> Option<(usize, usize)> {
+        self.find_iter(byte).next()
+    }
+
+    /// Finds every occurrence of `byte` in row-major order.
+    pub fn find_iter(&self, byte: u8) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let grid = *self;
+
+        memchr::memchr_iter(byte, self.data).filter_map(move |offset| grid.position(offset))
+    }
+
+    pub fn neighbours_iter_basis(&self, pos: &(usize, usize)) -> ByteGridNeighbourIterator<'a> {
+        self.neighbours_iter(pos, NeighbourTypes::BasisSet)
+    }
+
+    pub fn neighbours_iter<N: Neighbourhood>(
+        &self,
+        &(x, y): &(usize, usize),
+        include: N,
+    ) -> ByteGridNeighbourIterator<'a, N> {
+        assert!(x < self.width && y < self.height);
+
+        ByteGridNeighbourIterator {
+            grid: *self,
+            x,
+            y,
+            index: 0,
+            include,
+        }
+    }
+}
+
+impl GridRead<u8> for ByteGrid<'_> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn get(&self, x: usize, y: usize) -> Option<&u8> {
+        ByteGrid::get(self, x, y)
+    }
+}
+
+impl Display for ByteGrid<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            let row = &self.data[self.offset(0, y)..self.offset(self.width, y)];
+            writeln!(f, "{}", String::from_utf8_lossy(row))?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct ByteGridNeighbourIterator<'a, N = NeighbourTypes> {
+    grid: ByteGrid<'a>,
+    x: usize,
+    y: usize,
+    index: usize,
+    include: N,
+}
+
+impl<'a, N: Neighbourhood> ByteGridNeighbourIterator<'a, N> {
+    /// Yields each neighbour together with the (dx, dy) offset that produced it.
+    pub fn with_offsets(mut self) -> impl Iterator<Item = NeighbourWithOffset<'a, u8>> {
+        std::iter::from_fn(move || self.next_with_offset())
+    }
+
+    fn next_with_offset(&mut self) -> Option<NeighbourWithOffset<'a, u8>> {
+        let ((x, y), offset) = next_neighbour(
+            self.include.offsets(),
+            &mut self.index,
+            (self.x, self.y),
+            (self.grid.width, self.grid.height),
+        )?;
+
+        Some(((x, y), offset, &self.grid.data[self.grid.offset(x, y)]))
+    }
+}
+
+impl<'a, N: Neighbourhood> Iterator for ByteGridNeighbourIterator<'a, N> {
+    type Item = ((usize, usize), &'a u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_offset().map(|(pos, _, v)| (pos, v))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::ByteGrid;
+
+    #[test]
+    fn maps_offsets_to_positions() {
+        let input = "ab.\n.b.\n..b\n";
+        let grid = ByteGrid::parse(input).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.get(2, 2), Some(&b'b'));
+        assert_eq!(grid.position(grid.offset(1, 2)), Some((1, 2)));
+        assert_eq!(grid.position(3), None);
+        assert_eq!(
+            grid.find_iter(b'b').collect::<Vec<_>>(),
+            [(1, 0), (1, 1), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn rejects_ragged_input() {
+        assert!(ByteGrid::parse("abc\nde\nfgh").is_err());
+        assert!(ByteGrid::parse("abc\nx\ny\nfgh\n").is_err());
+        assert!(ByteGrid::parse("").is_err());
+    }
+}
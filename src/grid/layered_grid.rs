@@ -0,0 +1,203 @@
+use std::{collections::HashSet, fmt::Display};
+
+use super::{Grid, OverriddenFormatter};
+
+/// Something which moves over the terrain, occupying one or more cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entity<E> {
+    pub kind: E,
+    pub cells: Vec<(usize, usize)>,
+}
+
+/// A static terrain grid with a layer of movable entities on top of it, for puzzles where things
+/// are pushed around a map.
+pub struct LayeredGrid<T, E> {
+    pub terrain: Grid<T>,
+    entities: Vec<Entity<E>>,
+    occupancy: Grid<Option<usize>>,
+}
+
+impl<T, E> LayeredGrid<T, E>
+where
+    T: Clone,
+{
+    pub fn new(terrain: Grid<T>) -> Self {
+        let occupancy = Grid::new(None, terrain.width, terrain.height);
+
+        Self {
+            terrain,
+            entities: Vec::new(),
+            occupancy,
+        }
+    }
+
+    /// Places an entity over the given cells, returning its id. Fails if any of the cells are out
+    /// of range or already occupied.
+    pub fn add_entity(&mut self, kind: E, cells: Vec<(usize, usize)>) -> Result<usize, String> {
+        for &(x, y) in &cells {
+            match self.occupancy.get(x, y) {
+                None => return Err(format!("entity cell ({x}, {y}) is out of range")),
+                Some(Some(_)) => return Err(format!("entity cell ({x}, {y}) is occupied")),
+                Some(None) => {}
+            }
+        }
+
+        let id = self.entities.len();
+
+        for &(x, y) in &cells {
+            self.occupancy.set(x, y, Some(id));
+        }
+
+        self.entities.push(Entity { kind, cells });
+        Ok(id)
+    }
+
+    pub fn entity(&self, id: usize) -> &Entity<E> {
+        &self.entities[id]
+    }
+
+    pub fn entities(&self) -> impl Iterator<Item = (usize, &Entity<E>)> {
+        self.entities.iter().enumerate()
+    }
+
+    pub fn entity_at(&self, (x, y): (usize, usize)) -> Option<usize> {
+        *self.occupancy.get(x, y)?
+    }
+
+    /// Pushes an entity one step in the given direction, along with every entity in the chain in
+    /// front of it. Nothing moves unless every affected cell can move, i.e. no cell would leave the
+    /// grid or enter blocked terrain. Returns whether the push happened.
+    pub fn push<B: Fn(&T) -> bool>(
+        &mut self,
+        id: usize,
+        (dx, dy): (isize, isize),
+        blocked: B,
+    ) -> bool {
+        let mut moving = vec![id];
+        let mut seen = HashSet::from([id]);
+        let mut i = 0;
+
+        while let Some(&current) = moving.get(i) {
+            for &(x, y) in &self.entities[current].cells {
+                let target = match (x.checked_add_signed(dx), y.checked_add_signed(dy)) {
+                    (Some(tx), Some(ty)) => (tx, ty),
+                    _ => return false,
+                };
+
+                match self.terrain.get(target.0, target.1) {
+                    Some(terrain) if !blocked(terrain) => {}
+                    _ => return false,
+                }
+
+                if let Some(other) = self.entity_at(target) {
+                    if seen.insert(other) {
+                        moving.push(other);
+                    }
+                }
+            }
+
+            i += 1;
+        }
+
+        // every cell is free to move, so commit all entities at once
+        for &id in &moving {
+            for &(x, y) in &self.entities[id].cells {
+                self.occupancy.set(x, y, None);
+            }
+        }
+
+        for &id in &moving {
+            for cell in &mut self.entities[id].cells {
+                *cell = (
+                    cell.0.wrapping_add_signed(dx),
+                    cell.1.wrapping_add_signed(dy),
+                );
+                self.occupancy.set(cell.0, cell.1, Some(id));
+            }
+        }
+
+        true
+    }
+}
+
+impl<T, E> LayeredGrid<T, E>
+where
+    T: Clone + Display,
+{
+    /// Renders the terrain with entities drawn over it. `render` is given the entity's kind and the
+    /// index of the cell being drawn within the entity.
+    pub fn fmt_with_entities<'a, R: Fn(&E, usize) -> char + 'a>(
+        &'a self,
+        render: R,
+    ) -> OverriddenFormatter<'a, T, impl Fn(&(usize, usize)) -> Option<char> + 'a> {
+        self.terrain.fmt_with_overrides(move |&pos| {
+            let entity = &self.entities[self.entity_at(pos)?];
+            let index = entity.cells.iter().position(|&cell| cell == pos)?;
+
+            Some(render(&entity.kind, index))
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::LayeredGrid;
+    use crate::grid::Grid;
+
+    #[derive(Debug, PartialEq)]
+    enum Kind {
+        Robot,
+        Box,
+    }
+
+    fn render(kind: &Kind, index: usize) -> char {
+        match (kind, index) {
+            (Kind::Robot, _) => '@',
+            (Kind::Box, 0) => '[',
+            (Kind::Box, _) => ']',
+        }
+    }
+
+    fn warehouse() -> (LayeredGrid<char, Kind>, usize) {
+        let terrain = Grid::<char>::parse("#######\n#.....#\n#.....#\n#.....#\n#.....#\n#######");
+        let mut grid = LayeredGrid::new(terrain.unwrap());
+
+        grid.add_entity(Kind::Box, vec![(2, 3), (3, 3)]).unwrap();
+        grid.add_entity(Kind::Box, vec![(3, 2), (4, 2)]).unwrap();
+        let robot = grid.add_entity(Kind::Robot, vec![(3, 4)]).unwrap();
+
+        (grid, robot)
+    }
+
+    #[test]
+    fn pushes_chains_of_wide_entities() {
+        let (mut grid, robot) = warehouse();
+
+        assert!(grid.push(robot, (0, -1), |&c| c == '#'));
+        assert_eq!(grid.entity_at((2, 2)), Some(0));
+        assert_eq!(grid.entity_at((4, 1)), Some(1));
+        assert_eq!(grid.entity_at((3, 3)), Some(robot));
+        assert_eq!(grid.entity_at((3, 4)), None);
+    }
+
+    #[test]
+    fn blocks_whole_chain_atomically() {
+        let (mut grid, robot) = warehouse();
+        grid.push(robot, (0, -1), |&c| c == '#');
+
+        // the upper box would now hit the wall, so nothing in the chain may move
+        assert!(!grid.push(robot, (0, -1), |&c| c == '#'));
+        assert_eq!(
+            grid.fmt_with_entities(render).to_string(),
+            "#######\n#..[].#\n#.[]..#\n#..@..#\n#.....#\n#######\n"
+        );
+    }
+
+    #[test]
+    fn rejects_overlapping_entities() {
+        let (mut grid, _) = warehouse();
+
+        assert!(grid.add_entity(Kind::Box, vec![(4, 2)]).is_err());
+        assert!(grid.add_entity(Kind::Box, vec![(7, 0)]).is_err());
+    }
+}
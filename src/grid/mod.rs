@@ -0,0 +1,893 @@
+use std::{collections::HashMap, fmt::Display, hash::Hash};
+
+use bitflags::bitflags;
+
+pub use array_grid::*;
+pub use byte_grid::*;
+pub use layered_grid::*;
+pub use padded_grid::*;
+
+mod array_grid;
+mod byte_grid;
+mod layered_grid;
+mod padded_grid;
+
+#[derive(Clone)]
+pub struct Grid<T> {
+    pub data: Vec<T>,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    pub fn new(default: T, width: usize, height: usize) -> Self {
+        let data = vec![default; width * height];
+
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        self.data.get(y * self.width + x)
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        self.data.get_mut(y * self.width + x)
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn iter(&self) -> GridIterator<'_, T> {
+        GridIterator {
+            grid: self,
+            x: 0,
+            y: 0,
+        }
+    }
+
+    pub fn neighbours_iter_basis(&self, &(x, y): &(usize, usize)) -> GridNeighbourIterator<'_, T> {
+        self.neighbours_iter(&(x, y), NeighbourTypes::BasisSet)
+    }
+
+    pub fn neighbours_iter<N: Neighbourhood>(
+        &self,
+        &(x, y): &(usize, usize),
+        include: N,
+    ) -> GridNeighbourIterator<'_, T, N> {
+        assert!(x < self.width && y < self.height);
+
+        GridNeighbourIterator {
+            grid: self,
+            x,
+            y,
+            index: 0,
+            include,
+        }
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: T) {
+        if x >= self.width || y >= self.height {
+            panic!("out of range");
+        }
+
+        self.data[y * self.width + x] = value;
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: PartialEq,
+{
+    /// Position of the first cell equal to `value`, in row-major order.
+    pub fn find(&self, value: &T) -> Option<(usize, usize)> {
+        self.position(|v| v == value)
+    }
+
+    /// Positions of every cell equal to `value`, in row-major order.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.data
+            .iter()
+            .enumerate()
+            .filter(move |(_, v)| *v == value)
+            .map(|(i, _)| self.position_of(i))
+    }
+
+    /// Position of the first cell matching the predicate, in row-major order.
+    pub fn position<P: Fn(&T) -> bool>(&self, predicate: P) -> Option<(usize, usize)> {
+        self.data
+            .iter()
+            .position(predicate)
+            .map(|i| self.position_of(i))
+    }
+
+    pub fn count(&self, value: &T) -> usize {
+        self.data.iter().filter(|v| *v == value).count()
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone + Eq + Hash,
+{
+    /// Number of cells holding each distinct value.
+    pub fn histogram(&self) -> HashMap<T, usize> {
+        let mut histogram = HashMap::new();
+
+        for value in &self.data {
+            *histogram.entry(value.clone()).or_insert(0) += 1;
+        }
+
+        histogram
+    }
+
+    /// Positions of the cells holding each distinct value, in row-major order.
+    pub fn group_positions_by_value(&self) -> HashMap<T, Vec<(usize, usize)>> {
+        let mut groups: HashMap<T, Vec<(usize, usize)>> = HashMap::new();
+
+        for (i, value) in self.data.iter().enumerate() {
+            groups
+                .entry(value.clone())
+                .or_default()
+                .push(self.position_of(i));
+        }
+
+        groups
+    }
+}
+
+impl<T> Grid<T> {
+    fn position_of(&self, index: usize) -> (usize, usize) {
+        (index % self.width, index / self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = GridLine<'_, T>> {
+        (0..self.height).map(move |y| GridLine::new(self, (0, y), (1, 0), self.width))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = GridLine<'_, T>> {
+        (0..self.width).map(move |x| GridLine::new(self, (x, 0), (0, 1), self.height))
+    }
+
+    /// Lines running down and to the right, starting from the bottom-left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = GridLine<'_, T>> {
+        let starts = (0..self.height)
+            .rev()
+            .map(|y| (0, y))
+            .chain((1..self.width).map(|x| (x, 0)));
+
+        starts.map(move |(x, y)| {
+            GridLine::new(self, (x, y), (1, 1), (self.width - x).min(self.height - y))
+        })
+    }
+
+    /// Lines running down and to the left, starting from the top-left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = GridLine<'_, T>> {
+        let starts = (0..self.width)
+            .map(|x| (x, 0))
+            // a grid without columns has no right edge to start from.
+            .chain(
+                (1..self.height)
+                    .filter(|_| self.width > 0)
+                    .map(|y| (self.width - 1, y)),
+            );
+
+        starts.map(move |(x, y)| GridLine::new(self, (x, y), (-1, 1), (x + 1).min(self.height - y)))
+    }
+
+    /// Concentric rings walked clockwise from their top-left corner, outermost first.
+    pub fn rings(&self) -> impl Iterator<Item = GridRing<'_, T>> {
+        (0..self.width.min(self.height).div_ceil(2)).map(move |ring| GridRing::new(self, ring))
+    }
+
+    pub fn iter_columns(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.columns().flatten()
+    }
+
+    /// Walks the grid clockwise from the top-left corner, spiralling inwards.
+    pub fn iter_spiral(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.rings().flatten()
+    }
+}
+
+/// Read access shared by all grid backends, so that puzzle code can be written once and run
+/// against whichever layout is fastest.
+pub trait GridRead<T> {
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+    fn get(&self, x: usize, y: usize) -> Option<&T>;
+
+    /// The cell at `(x, y)` moved by `(dx, dy)`, or `None` if that leaves the grid.
+    fn step(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<&T> {
+        self.get(x.checked_add_signed(dx)?, y.checked_add_signed(dy)?)
+    }
+}
+
+/// Write access shared by all mutable grid backends.
+pub trait GridWrite<T>: GridRead<T> {
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T>;
+
+    fn set(&mut self, x: usize, y: usize, value: T) {
+        *self.get_mut(x, y).expect("out of range") = value;
+    }
+}
+
+impl<T: Clone> GridRead<T> for Grid<T> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn get(&self, x: usize, y: usize) -> Option<&T> {
+        Grid::get(self, x, y)
+    }
+}
+
+impl<T: Clone> GridWrite<T> for Grid<T> {
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        Grid::get_mut(self, x, y)
+    }
+
+    fn set(&mut self, x: usize, y: usize, value: T) {
+        Grid::set(self, x, y, value)
+    }
+}
+
+bitflags! {
+    pub struct NeighbourTypes: u8 {
+        const Horizontal = 0x1;
+        const Vertical = 0x2;
+        const Diagonals = 0x4;
+        const BasisSet = Self::Horizontal.bits() | Self::Vertical.bits();
+    }
+}
+
+/// A set of (dx, dy) offsets that can be visited from a position with `neighbours_iter`.
+pub trait Neighbourhood {
+    fn offsets(&self) -> &[(isize, isize)];
+}
+
+impl Neighbourhood for NeighbourTypes {
+    fn offsets(&self) -> &[(isize, isize)] {
+        match (
+            self.contains(NeighbourTypes::Horizontal),
+            self.contains(NeighbourTypes::Vertical),
+            self.contains(NeighbourTypes::Diagonals),
+        ) {
+            (true, true, true) => &NEIGHBOURS,
+            (true, true, false) => &NEIGHBOURS[..4],
+            (true, false, true) => &HORIZONTAL_DIAGONAL_NEIGHBOURS,
+            (true, false, false) => &NEIGHBOURS[..2],
+            (false, true, true) => &NEIGHBOURS[2..],
+            (false, true, false) => &NEIGHBOURS[2..4],
+            (false, false, true) => &NEIGHBOURS[4..],
+            (false, false, false) => &[],
+        }
+    }
+}
+
+impl<N: Neighbourhood + ?Sized> Neighbourhood for &N {
+    fn offsets(&self) -> &[(isize, isize)] {
+        (**self).offsets()
+    }
+}
+
+/// A user-defined neighbourhood, for moves that are not covered by `NeighbourTypes`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stencil {
+    offsets: Vec<(isize, isize)>,
+}
+
+impl Stencil {
+    pub fn new<I: IntoIterator<Item = (isize, isize)>>(offsets: I) -> Self {
+        Self {
+            offsets: offsets.into_iter().collect(),
+        }
+    }
+
+    pub fn knight() -> Self {
+        Self::new([
+            (-2, -1),
+            (-2, 1),
+            (-1, -2),
+            (-1, 2),
+            (1, -2),
+            (1, 2),
+            (2, -1),
+            (2, 1),
+        ])
+    }
+
+    /// All offsets within the given Manhattan distance, excluding the origin.
+    pub fn manhattan(radius: usize) -> Self {
+        Self::new((1..=radius).flat_map(Self::manhattan_offsets))
+    }
+
+    /// All offsets at exactly the given Manhattan distance. Empty for a distance of 0, as the
+    /// origin is no neighbour of itself.
+    pub fn manhattan_exact(distance: usize) -> Self {
+        Self::new(Self::manhattan_offsets(distance))
+    }
+
+    fn manhattan_offsets(distance: usize) -> impl Iterator<Item = (isize, isize)> {
+        let k = distance as isize;
+
+        (-k..=k).filter(move |_| k > 0).flat_map(move |dy| {
+            let dx = k - dy.abs();
+            // the top and bottom rows hold a single offset on the vertical axis.
+            let count = if dx == 0 { 1 } else { 2 };
+
+            [(-dx, dy), (dx, dy)].into_iter().take(count)
+        })
+    }
+}
+
+impl Neighbourhood for Stencil {
+    fn offsets(&self) -> &[(isize, isize)] {
+        &self.offsets
+    }
+}
+
+impl From<NeighbourTypes> for Stencil {
+    fn from(value: NeighbourTypes) -> Self {
+        Self::new(value.offsets().iter().copied())
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone + Default + From<char>,
+{
+    // parse assumes the grid is rectangular with constant width
+    pub fn parse<Input: AsRef<str>>(input: Input) -> Result<Self, String> {
+        Self::parse_with_parser(Default::default(), input, |character| character.into())
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    pub fn parse_with_parser<Input: AsRef<str>, Parser: Fn(char) -> T>(
+        default: T,
+        input: Input,
+        parser: Parser,
+    ) -> Result<Self, String> {
+        let (width, height) =
+            input
+                .as_ref()
+                .lines()
+                .try_fold((None, 0), |(width, height), line| match width {
+                    Some(x) if x != line.len() => Err("grid is not rectangular"),
+                    _ => Ok((Some(line.len()), height + 1)),
+                })?;
+
+        if width.is_none() {
+            return Err("grid is empty".to_string());
+        }
+
+        let mut grid = Self::new(default, width.unwrap(), height);
+
+        for (y, line) in input.as_ref().lines().enumerate() {
+            for (x, character) in line.chars().enumerate() {
+                grid.set(x, y, parser(character));
+            }
+        }
+
+        Ok(grid)
+    }
+}
+
+impl<T> std::fmt::Display for Grid<T>
+where
+    T: Clone + Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ((x, _), value) in self {
+            write!(f, "{}", value)?;
+
+            if x == self.width - 1 {
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone + Display,
+{
+    pub fn fmt_with_overrides<'a, F: Fn(&(usize, usize)) -> Option<char> + 'a>(
+        &'a self,
+        overrides: F,
+    ) -> OverriddenFormatter<'a, T, F> {
+        OverriddenFormatter {
+            grid: self,
+            overrides,
+        }
+    }
+}
+
+pub struct OverriddenFormatter<'a, T, F: Fn(&(usize, usize)) -> Option<char>> {
+    grid: &'a Grid<T>,
+    overrides: F,
+}
+
+impl<T, F> Display for OverriddenFormatter<'_, T, F>
+where
+    T: Clone + Display,
+    F: Fn(&(usize, usize)) -> Option<char>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ((x, y), value) in self.grid {
+            if let Some(override_char) = (self.overrides)(&(x, y)) {
+                write!(f, "{}", override_char)?;
+            } else {
+                write!(f, "{}", value)?;
+            }
+
+            if x == self.grid.width - 1 {
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct GridIterator<'a, T> {
+    grid: &'a Grid<T>,
+    x: usize,
+    y: usize,
+}
+
+impl<T> Iterator for GridIterator<'_, T>
+where
+    T: Clone,
+{
+    type Item = ((usize, usize), T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.y >= self.grid.height() {
+            return None;
+        }
+
+        let value = self.grid.get(self.x, self.y).unwrap().clone();
+        let position = (self.x, self.y);
+
+        self.x += 1;
+
+        if self.x >= self.grid.width() {
+            self.x = 0;
+            self.y += 1;
+        }
+
+        Some((position, value))
+    }
+}
+
+pub struct GridIntoIterator<'a, T> {
+    grid: &'a Grid<T>,
+    x: usize,
+    y: usize,
+}
+
+impl<T> Iterator for GridIntoIterator<'_, T>
+where
+    T: Clone,
+{
+    type Item = ((usize, usize), T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.y >= self.grid.height() {
+            return None;
+        }
+
+        let value = self.grid.get(self.x, self.y).unwrap().clone();
+        let position = (self.x, self.y);
+
+        self.x += 1;
+
+        if self.x >= self.grid.width() {
+            self.x = 0;
+            self.y += 1;
+        }
+
+        Some((position, value))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Grid<T>
+where
+    T: Clone,
+{
+    type Item = ((usize, usize), T);
+    type IntoIter = GridIntoIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        GridIntoIterator {
+            grid: self,
+            x: 0,
+            y: 0,
+        }
+    }
+}
+
+pub struct GridNeighbourIterator<'a, T, N = NeighbourTypes> {
+    grid: &'a Grid<T>,
+    x: usize,
+    y: usize,
+    index: usize,
+    include: N,
+}
+
+static NEIGHBOURS: [(isize, isize); 8] = [
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (-1, 1),
+    (1, -1),
+    (1, 1),
+];
+
+static HORIZONTAL_DIAGONAL_NEIGHBOURS: [(isize, isize); 6] =
+    [(-1, 0), (1, 0), (-1, -1), (-1, 1), (1, -1), (1, 1)];
+
+impl<'a, T, N> GridNeighbourIterator<'a, T, N>
+where
+    N: Neighbourhood,
+{
+    /// Yields each neighbour together with the (dx, dy) offset that produced it.
+    pub fn with_offsets(self) -> GridNeighbourOffsetIterator<'a, T, N> {
+        GridNeighbourOffsetIterator { inner: self }
+    }
+
+    fn next_with_offset(&mut self) -> Option<NeighbourWithOffset<'a, T>> {
+        let ((x, y), offset) = next_neighbour(
+            self.include.offsets(),
+            &mut self.index,
+            (self.x, self.y),
+            (self.grid.width, self.grid.height),
+        )?;
+
+        Some(((x, y), offset, &self.grid.data[y * self.grid.width + x]))
+    }
+}
+
+/// Advances `index` through `offsets` to the next neighbour of `(x, y)` which lies inside a grid
+/// of the given dimensions.
+fn next_neighbour(
+    offsets: &[(isize, isize)],
+    index: &mut usize,
+    (x, y): (usize, usize),
+    (width, height): (usize, usize),
+) -> Option<((usize, usize), (isize, isize))> {
+    while let Some(&(dx, dy)) = offsets.get(*index) {
+        *index += 1;
+
+        let nx = x as isize + dx;
+        let ny = y as isize + dy;
+
+        if nx < 0 || ny < 0 || nx >= width as isize || ny >= height as isize {
+            continue;
+        }
+
+        return Some(((nx as usize, ny as usize), (dx, dy)));
+    }
+
+    None
+}
+
+impl<'a, T, N> Iterator for GridNeighbourIterator<'a, T, N>
+where
+    N: Neighbourhood,
+{
+    type Item = ((usize, usize), &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_offset().map(|(pos, _, v)| (pos, v))
+    }
+}
+
+type NeighbourWithOffset<'a, T> = ((usize, usize), (isize, isize), &'a T);
+
+pub struct GridNeighbourOffsetIterator<'a, T, N = NeighbourTypes> {
+    inner: GridNeighbourIterator<'a, T, N>,
+}
+
+impl<'a, T, N> Iterator for GridNeighbourOffsetIterator<'a, T, N>
+where
+    N: Neighbourhood,
+{
+    type Item = NeighbourWithOffset<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_with_offset()
+    }
+}
+
+/// A straight line of cells through the grid, such as a row, column or diagonal.
+pub struct GridLine<'a, T> {
+    grid: &'a Grid<T>,
+    start: (usize, usize),
+    step: (isize, isize),
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> GridLine<'a, T> {
+    fn new(grid: &'a Grid<T>, start: (usize, usize), step: (isize, isize), len: usize) -> Self {
+        Self {
+            grid,
+            start,
+            step,
+            front: 0,
+            back: len,
+        }
+    }
+
+    fn at(&self, i: usize) -> ((usize, usize), &'a T) {
+        let x = (self.start.0 as isize + self.step.0 * i as isize) as usize;
+        let y = (self.start.1 as isize + self.step.1 * i as isize) as usize;
+
+        ((x, y), &self.grid.data[y * self.grid.width + x])
+    }
+}
+
+impl<'a, T> Iterator for GridLine<'a, T> {
+    type Item = ((usize, usize), &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        self.front += 1;
+        Some(self.at(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for GridLine<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(self.at(self.back))
+    }
+}
+
+impl<T> ExactSizeIterator for GridLine<'_, T> {}
+
+/// The cells on one ring of the grid, walked clockwise from its top-left corner.
+pub struct GridRing<'a, T> {
+    grid: &'a Grid<T>,
+    top_left: (usize, usize),
+    bottom_right: (usize, usize),
+    index: usize,
+    len: usize,
+}
+
+impl<'a, T> GridRing<'a, T> {
+    fn new(grid: &'a Grid<T>, ring: usize) -> Self {
+        let top_left = (ring, ring);
+        let bottom_right = (grid.width - 1 - ring, grid.height - 1 - ring);
+        let (w, h) = (bottom_right.0 - top_left.0, bottom_right.1 - top_left.1);
+
+        // a ring which has collapsed to a single row or column has no way back round
+        let len = if w == 0 || h == 0 {
+            w + h + 1
+        } else {
+            2 * (w + h)
+        };
+
+        Self {
+            grid,
+            top_left,
+            bottom_right,
+            index: 0,
+            len,
+        }
+    }
+
+    fn position_at(&self, mut i: usize) -> (usize, usize) {
+        let ((x0, y0), (x1, y1)) = (self.top_left, self.bottom_right);
+
+        if y0 == y1 {
+            return (x0 + i, y0);
+        }
+        if x0 == x1 {
+            return (x0, y0 + i);
+        }
+
+        if i < x1 - x0 {
+            return (x0 + i, y0);
+        }
+        i -= x1 - x0;
+
+        if i < y1 - y0 {
+            return (x1, y0 + i);
+        }
+        i -= y1 - y0;
+
+        if i < x1 - x0 {
+            return (x1 - i, y1);
+        }
+        i -= x1 - x0;
+
+        (x0, y1 - i)
+    }
+}
+
+impl<'a, T> Iterator for GridRing<'a, T> {
+    type Item = ((usize, usize), &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.len {
+            return None;
+        }
+
+        let (x, y) = self.position_at(self.index);
+        self.index += 1;
+
+        Some(((x, y), &self.grid.data[y * self.grid.width + x]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for GridRing<'_, T> {}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{Grid, Neighbourhood, Stencil};
+
+    fn collect<'a>(line: impl Iterator<Item = ((usize, usize), &'a char)>) -> String {
+        line.map(|(_, c)| *c).collect()
+    }
+
+    #[test]
+    fn walks_diagonals() {
+        let grid = Grid::<char>::parse("abc\ndef").unwrap();
+
+        let diagonals: Vec<_> = grid.diagonals().map(collect).collect();
+        assert_eq!(diagonals, ["d", "ae", "bf", "c"]);
+
+        let anti_diagonals: Vec<_> = grid.anti_diagonals().map(collect).collect();
+        assert_eq!(anti_diagonals, ["a", "bd", "ce", "f"]);
+    }
+
+    #[test]
+    fn walks_diagonals_of_empty_grids() {
+        let grid = Grid::new('.', 0, 3);
+
+        assert_eq!(grid.anti_diagonals().count(), 0);
+        assert!(grid.diagonals().all(|line| line.count() == 0));
+    }
+
+    #[test]
+    fn walks_columns_and_reversed_lines() {
+        let grid = Grid::<char>::parse("abc\ndef").unwrap();
+
+        assert_eq!(collect(grid.iter_columns()), "adbecf");
+        assert_eq!(collect(grid.rows().next().unwrap().rev()), "cba");
+    }
+
+    #[test]
+    fn walks_spiral() {
+        let grid = Grid::<char>::parse("abcd\nefgh\nijkl").unwrap();
+
+        assert_eq!(grid.rings().count(), 2);
+        assert_eq!(collect(grid.iter_spiral()), "abcdhlkjiefg");
+    }
+
+    #[test]
+    fn queries_values() {
+        let grid = Grid::<char>::parse("#.#\n.^.\n#..").unwrap();
+
+        assert_eq!(grid.find(&'^'), Some((1, 1)));
+        assert_eq!(
+            grid.find_all(&'#').collect::<Vec<_>>(),
+            [(0, 0), (2, 0), (0, 2)]
+        );
+        assert_eq!(grid.position(|&c| c != '#'), Some((1, 0)));
+        assert_eq!(grid.count(&'.'), 5);
+        assert_eq!(grid.histogram()[&'#'], 3);
+        assert_eq!(grid.group_positions_by_value()[&'^'], [(1, 1)]);
+    }
+
+    #[test]
+    fn builds_stencils() {
+        let offsets = |stencil: Stencil| stencil.offsets().iter().copied().collect::<HashSet<_>>();
+
+        assert_eq!(Stencil::new([(3, 0), (0, -3)]).offsets(), [(3, 0), (0, -3)]);
+        assert_eq!(offsets(Stencil::knight()).len(), 8);
+
+        let manhattan = offsets(Stencil::manhattan(2));
+        assert_eq!(manhattan.len(), 12);
+        assert!(!manhattan.contains(&(0, 0)));
+        assert!(manhattan.contains(&(1, -1)) && manhattan.contains(&(0, 2)));
+
+        let exact = offsets(Stencil::manhattan_exact(2));
+        assert_eq!(exact.len(), 8);
+        assert!(exact.iter().all(|(dx, dy)| dx.abs() + dy.abs() == 2));
+
+        assert!(Stencil::manhattan_exact(0).offsets().is_empty());
+        assert!(Stencil::manhattan(0).offsets().is_empty());
+    }
+
+    #[test]
+    fn moves_knight_from_corner() {
+        let grid = Grid::new('.', 4, 4);
+
+        let moves: HashSet<_> = grid
+            .neighbours_iter(&(0, 0), Stencil::knight())
+            .map(|(pos, _)| pos)
+            .collect();
+        assert_eq!(moves, HashSet::from([(1, 2), (2, 1)]));
+
+        let moves = grid.neighbours_iter(&(3, 3), Stencil::knight()).count();
+        assert_eq!(moves, 2);
+    }
+
+    #[test]
+    fn clips_stencils_beyond_one_at_edges() {
+        let grid = Grid::new('.', 5, 5);
+        let count = |pos, stencil| grid.neighbours_iter(&pos, stencil).count();
+
+        // from a corner, only (1, 0), (2, 0), (0, 1), (1, 1) and (0, 2) remain.
+        assert_eq!(count((0, 0), Stencil::manhattan(2)), 5);
+        assert_eq!(count((2, 2), Stencil::manhattan(2)), 12);
+        assert_eq!(count((1, 4), Stencil::manhattan_exact(2)), 4);
+    }
+
+    #[test]
+    fn yields_producing_offsets() {
+        let grid = Grid::<char>::parse("abcd\nefgh\nijkl").unwrap();
+
+        let neighbours: Vec<_> = grid
+            .neighbours_iter(&(0, 0), Stencil::new([(-2, 0), (2, 0), (3, 2), (0, 3)]))
+            .with_offsets()
+            .map(|(pos, offset, value)| (pos, offset, *value))
+            .collect();
+
+        assert_eq!(neighbours, [((2, 0), (2, 0), 'c'), ((3, 2), (3, 2), 'l')]);
+    }
+}
@@ -0,0 +1,131 @@
+use super::{Grid, GridRead, GridWrite};
+
+/// A grid surrounded by a one cell border of a sentinel value. Stepping one cell off the playfield
+/// with `step_unchecked` lands on the sentinel rather than out of bounds, so walkers can check for
+/// the sentinel instead of checking coordinates. Through `GridRead`, the border is out of bounds as
+/// it is for any other grid.
+#[derive(Clone)]
+pub struct PaddedGrid<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> PaddedGrid<T>
+where
+    T: Clone,
+{
+    pub fn new(default: T, sentinel: T, width: usize, height: usize) -> Self {
+        let mut data = vec![sentinel; (width + 2) * (height + 2)];
+
+        for y in 1..=height {
+            data[y * (width + 2) + 1..=y * (width + 2) + width].fill(default.clone());
+        }
+
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+
+    pub fn from_grid(grid: &Grid<T>, sentinel: T) -> Self {
+        let mut padded = Self::new(sentinel.clone(), sentinel, grid.width, grid.height);
+
+        for (y, row) in grid.data.chunks(grid.width.max(1)).enumerate() {
+            let start = padded.index(0, y);
+            padded.data[start..start + grid.width].clone_from_slice(row);
+        }
+
+        padded
+    }
+}
+
+impl<T> PaddedGrid<T> {
+    fn index(&self, x: usize, y: usize) -> usize {
+        (y + 1) * (self.width + 2) + x + 1
+    }
+
+    /// The cell one step of at most one in each direction from `(x, y)`, which is the sentinel if
+    /// it is off the playfield. `(x, y)` must be on the playfield, which is only checked in debug
+    /// builds: from elsewhere, the step wraps around into another row.
+    pub fn step_unchecked(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> &T {
+        debug_assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is off the playfield"
+        );
+        debug_assert!(
+            dx.abs() <= 1 && dy.abs() <= 1,
+            "({dx}, {dy}) is no unit step"
+        );
+
+        let index = self.index(x, y) as isize + dy * (self.width + 2) as isize + dx;
+        &self.data[index as usize]
+    }
+}
+
+impl<T> GridRead<T> for PaddedGrid<T> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some(&self.data[self.index(x, y)])
+    }
+}
+
+impl<T> GridWrite<T> for PaddedGrid<T> {
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let index = self.index(x, y);
+        Some(&mut self.data[index])
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PaddedGrid;
+    use crate::grid::{Grid, GridRead};
+
+    #[test]
+    fn steps_onto_sentinel_border() {
+        let grid = Grid::<char>::parse("ab\ncd").unwrap();
+        let padded = PaddedGrid::from_grid(&grid, '*');
+
+        assert_eq!(padded.get(1, 1), Some(&'d'));
+        assert_eq!(padded.step_unchecked((0, 0), (1, 1)), &'d');
+        assert_eq!(padded.step_unchecked((0, 0), (-1, 0)), &'*');
+        assert_eq!(padded.step_unchecked((1, 1), (1, 1)), &'*');
+    }
+
+    #[test]
+    fn steps_off_playfield_like_grid() {
+        let grid = Grid::<char>::parse("ab\ncd").unwrap();
+        let padded = PaddedGrid::from_grid(&grid, '*');
+
+        for offset in [(1, 1), (-1, 0), (0, 1), (2, 0)] {
+            for pos in [(0, 0), (1, 1)] {
+                assert_eq!(padded.step(pos, offset), grid.step(pos, offset));
+            }
+        }
+    }
+
+    #[test]
+    fn pads_empty_grids() {
+        let padded = PaddedGrid::from_grid(&Grid::new('.', 0, 2), '*');
+
+        assert_eq!((padded.width(), padded.height()), (0, 2));
+        assert_eq!(padded.get(0, 0), None);
+    }
+}
@@ -1,4 +1,7 @@
 pub mod template;
+pub use template::{error::Error, progress, viz};
 
 // Use this file to add helper functions and additional modules.
+pub mod graph;
 pub mod grid;
+pub mod ocr;
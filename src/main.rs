@@ -7,8 +7,12 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        bench_options::{parse_duration, parse_samples, BenchOptions},
+        limits::{parse_bytes, Limits},
+        Day,
+    };
+    use std::{process, time::Duration};
 
     pub enum AppArguments {
         Download {
@@ -26,15 +30,32 @@ mod args {
             day: Day,
             release: bool,
             dhat: bool,
+            alloc_stats: bool,
             submit: Option<u8>,
+            all_inputs: bool,
+            input: Option<String>,
+            visualize: bool,
+            viz_delay: Option<Duration>,
+            verbosity: u8,
+            bench_options: BenchOptions,
+            limits: Limits,
+            json: bool,
         },
         All {
             release: bool,
+            alloc_stats: bool,
+            bench_options: BenchOptions,
+            limits: Limits,
+            json: bool,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            alloc_stats: bool,
+            bench_options: BenchOptions,
+            limits: Limits,
+            json: bool,
         },
         #[cfg(feature = "today")]
         Today,
@@ -46,15 +67,27 @@ mod args {
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                alloc_stats: args.contains("--alloc-stats"),
+                bench_options: parse_bench_options(&mut args)?,
+                limits: parse_limits(&mut args)?,
+                json: args.contains("--json"),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let alloc_stats = args.contains("--alloc-stats");
+                let bench_options = parse_bench_options(&mut args)?;
+                let limits = parse_limits(&mut args)?;
+                let json = args.contains("--json");
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    alloc_stats,
+                    bench_options,
+                    limits,
+                    json,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -72,7 +105,16 @@ mod args {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
+                all_inputs: args.contains("--all-inputs"),
+                input: args.opt_value_from_str("--input")?,
+                visualize: args.contains("--visualize"),
+                viz_delay: args.opt_value_from_fn("--viz-delay", parse_duration)?,
+                verbosity: parse_verbosity(&mut args),
                 dhat: args.contains("--dhat"),
+                alloc_stats: args.contains("--alloc-stats"),
+                bench_options: parse_bench_options(&mut args)?,
+                limits: parse_limits(&mut args)?,
+                json: args.contains("--json"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -93,6 +135,33 @@ mod args {
 
         Ok(app_args)
     }
+
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, pico_args::Error> {
+        Ok(Limits {
+            timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+            memory: args.opt_value_from_fn("--memory-limit", parse_bytes)?,
+        })
+    }
+
+    fn parse_verbosity(args: &mut pico_args::Arguments) -> u8 {
+        if args.contains("-vv") {
+            2
+        } else if args.contains("-v") {
+            1
+        } else {
+            0
+        }
+    }
+
+    fn parse_bench_options(
+        args: &mut pico_args::Arguments,
+    ) -> Result<BenchOptions, pico_args::Error> {
+        Ok(BenchOptions {
+            time: args.opt_value_from_fn("--bench-time", parse_duration)?,
+            min_samples: args.opt_value_from_fn("--min-samples", parse_samples)?,
+            max_samples: args.opt_value_from_fn("--max-samples", parse_samples)?,
+        })
+    }
 }
 
 fn main() {
@@ -102,8 +171,22 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All {
+                release,
+                alloc_stats,
+                bench_options,
+                limits,
+                json,
+            } => all::handle(release, alloc_stats, &bench_options, &limits, json),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                alloc_stats,
+                bench_options,
+                limits,
+                json,
+            } => time::handle(day, all, store, alloc_stats, &bench_options, &limits, json),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
@@ -120,8 +203,31 @@ fn main() {
                 day,
                 release,
                 dhat,
+                alloc_stats,
+                submit,
+                all_inputs,
+                input,
+                visualize,
+                viz_delay,
+                verbosity,
+                bench_options,
+                limits,
+                json,
+            } => solve::handle(
+                day,
+                release,
+                dhat,
+                alloc_stats,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                all_inputs,
+                input.as_deref(),
+                visualize,
+                viz_delay,
+                verbosity,
+                &bench_options,
+                &limits,
+                json,
+            ),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
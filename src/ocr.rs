@@ -0,0 +1,205 @@
+/// Reads answers drawn in the block-letter fonts of Advent of Code, with letters 6 or 10
+/// pixels high. Lit pixels are drawn as `#` or `█`, unlit ones as `.` or a space.
+use crate::grid::Grid;
+
+static SMALL_FONT: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[rustfmt::skip]
+static LARGE_FONT: &[(char, [&str; 10])] = &[
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// Reads the letters drawn in `art`, or returns `None` if it is not made up of known letters.
+pub fn parse(art: &str) -> Option<String> {
+    let rows = art
+        .lines()
+        .map(|line| {
+            line.chars()
+                .map(|c| match c {
+                    '#' | '█' => Some(true),
+                    '.' | ' ' => Some(false),
+                    _ => None,
+                })
+                .collect()
+        })
+        .collect::<Option<Vec<Vec<bool>>>>()?;
+
+    read(rows)
+}
+
+/// Reads the letters drawn by the lit pixels of `grid`.
+pub fn parse_grid(grid: &Grid<bool>) -> Option<String> {
+    read(
+        grid.data
+            .chunks(grid.width.max(1))
+            .map(<[bool]>::to_vec)
+            .collect(),
+    )
+}
+
+/// Reads the letters in rows of pixels, which are split into letters on unlit columns.
+fn read(mut rows: Vec<Vec<bool>>) -> Option<String> {
+    let is_blank = |row: &Vec<bool>| !row.contains(&true);
+
+    // rows may be surrounded by blank rows and have their trailing unlit pixels left out.
+    while rows.first().is_some_and(is_blank) {
+        rows.remove(0);
+    }
+    while rows.last().is_some_and(is_blank) {
+        rows.pop();
+    }
+
+    let width = rows.iter().map(Vec::len).max()?;
+    for row in &mut rows {
+        row.resize(width, false);
+    }
+
+    let font: Vec<(char, Vec<Vec<bool>>)> = match rows.len() {
+        6 => SMALL_FONT
+            .iter()
+            .map(|(letter, glyph)| (*letter, pixels(glyph)))
+            .collect(),
+        10 => LARGE_FONT
+            .iter()
+            .map(|(letter, glyph)| (*letter, pixels(glyph)))
+            .collect(),
+        _ => return None,
+    };
+
+    let mut letters = String::new();
+    let mut x = 0;
+
+    while x < width {
+        if rows.iter().all(|row| !row[x]) {
+            x += 1;
+            continue;
+        }
+
+        let end = (x..width)
+            .find(|&end| rows.iter().all(|row| !row[end]))
+            .unwrap_or(width);
+        let glyph: Vec<Vec<bool>> = rows.iter().map(|row| row[x..end].to_vec()).collect();
+
+        letters.push(font.iter().find(|(_, pixels)| *pixels == glyph)?.0);
+        x = end;
+    }
+
+    (!letters.is_empty()).then_some(letters)
+}
+
+/// The pixels of a glyph of a font, without its unlit columns on either side.
+fn pixels(glyph: &[&str]) -> Vec<Vec<bool>> {
+    let lit = |x: usize| glyph.iter().any(|row| row.as_bytes()[x] == b'#');
+    let width = glyph[0].len();
+    let start = (0..width).find(|&x| lit(x)).unwrap_or(0);
+    let end = (0..width).rfind(|&x| lit(x)).map_or(width, |x| x + 1);
+
+    glyph
+        .iter()
+        .map(|row| row[start..end].bytes().map(|b| b == b'#').collect())
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, parse_grid, LARGE_FONT, SMALL_FONT};
+    use crate::grid::Grid;
+
+    /// Draws `letters` the way Advent of Code does, a column apart for the small font and two
+    /// columns apart for the large one.
+    fn draw<const N: usize>(font: &[(char, [&str; N])], letters: &str, gap: &str) -> String {
+        (0..N)
+            .map(|y| {
+                letters
+                    .chars()
+                    .map(|c| font.iter().find(|(letter, _)| *letter == c).unwrap().1[y])
+                    .collect::<Vec<_>>()
+                    .join(gap)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn reads_small_letters() {
+        let letters = "ABCEFGHIJKLOPRSUYZ";
+        assert_eq!(parse(&draw(SMALL_FONT, letters, ".")), Some(letters.into()));
+    }
+
+    #[test]
+    fn reads_large_letters() {
+        let letters = "ABCEFGHJKLNPRXZ";
+        assert_eq!(
+            parse(&draw(LARGE_FONT, letters, "..")),
+            Some(letters.into())
+        );
+    }
+
+    #[test]
+    fn reads_block_rendering() {
+        let art = "\n\
+            █  █ ███  █   \n\
+            █  █  █   █\n\
+            ████  █   █\n\
+            █  █  █   █\n\
+            █  █  █   █\n\
+            █  █ ███  ████\n";
+
+        assert_eq!(parse(art), Some("HIL".into()));
+    }
+
+    #[test]
+    fn reads_grid() {
+        let grid = Grid::<char>::parse(draw(SMALL_FONT, "OK", ".")).unwrap();
+        let lit = Grid {
+            data: grid.data.iter().map(|c| *c == '#').collect(),
+            width: grid.width,
+            height: grid.height,
+        };
+
+        assert_eq!(parse_grid(&lit), Some("OK".into()));
+    }
+
+    #[test]
+    fn rejects_unknown_drawings() {
+        assert_eq!(parse("42"), None);
+        assert_eq!(
+            parse(&draw(SMALL_FONT, "AB", ".").replace("###.", "#.#.")),
+            None
+        );
+        assert_eq!(parse(&"....\n".repeat(6)), None);
+    }
+}
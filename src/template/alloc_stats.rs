@@ -0,0 +1,219 @@
+/// A lightweight counting allocator, installed in solution binaries when the `alloc-stats` feature
+/// is enabled. Counters are kept per thread, so parallel tests do not skew each other's numbers;
+/// allocations made on other threads are not counted.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    collections::HashMap,
+    fmt::Display,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use tinyjson::JsonValue;
+
+/// Allocations made while running a step of a solution.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Total number of bytes allocated.
+    pub bytes: u64,
+    /// Number of allocations.
+    pub count: u64,
+    /// Highest number of bytes live at once, on top of what was live before.
+    pub peak: u64,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} in {} allocs, peak {}",
+            format_bytes(self.bytes),
+            self.count,
+            format_bytes(self.peak)
+        )
+    }
+}
+
+pub struct CountingAlloc;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static BYTES: Cell<u64> = const { Cell::new(0) };
+    static COUNT: Cell<u64> = const { Cell::new(0) };
+    static LIVE: Cell<i64> = const { Cell::new(0) };
+    static PEAK: Cell<i64> = const { Cell::new(0) };
+}
+
+fn record_alloc(size: usize) {
+    // only written once, so that counting does not keep contending for its cache line.
+    if !INSTALLED.load(Ordering::Relaxed) {
+        INSTALLED.store(true, Ordering::Relaxed);
+    }
+
+    // counters may already be gone while a thread shuts down, skip those allocations.
+    let _ = BYTES.try_with(|bytes| bytes.set(bytes.get() + size as u64));
+    let _ = COUNT.try_with(|count| count.set(count.get() + 1));
+    let _ = LIVE.try_with(|live| {
+        live.set(live.get() + size as i64);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+    });
+}
+
+fn record_dealloc(size: usize) {
+    let _ = LIVE.try_with(|live| live.set(live.get() - size as i64));
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Whether the counting allocator is installed and allocations are being counted.
+pub fn is_counting() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Runs `func`, counting the allocations it makes on the current thread. All counts are zero if
+/// the counting allocator is not installed.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, AllocStats) {
+    let (bytes, count, live) = (BYTES.get(), COUNT.get(), LIVE.get());
+    PEAK.set(live);
+
+    let result = func();
+
+    let stats = AllocStats {
+        bytes: BYTES.get() - bytes,
+        count: COUNT.get() - count,
+        peak: (PEAK.get() - live).max(0) as u64,
+    };
+
+    (result, stats)
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&AllocStats> for JsonValue {
+    fn from(value: &AllocStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+        map.insert("count".into(), JsonValue::Number(value.count as f64));
+        map.insert("peak".into(), JsonValue::Number(value.peak as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected alloc stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|n| n as u64)
+                .ok_or(format!("Expected alloc.{key} to be a number."))
+        };
+
+        Ok(AllocStats {
+            bytes: number("bytes")?,
+            count: number("count")?,
+            peak: number("peak")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Asserts that evaluating an expression allocates at most `$bytes` bytes, and evaluates to the
+/// value of the expression. Only checked when the `alloc-stats` feature is enabled.
+///
+/// ```ignore
+/// let result = advent_of_code::assert_alloc_budget!(part_one(&input), 1024);
+/// ```
+#[macro_export]
+macro_rules! assert_alloc_budget {
+    ($expr:expr, $bytes:expr) => {{
+        let (value, alloc) = $crate::template::alloc_stats::measure(|| $expr);
+        assert!(
+            alloc.bytes <= $bytes,
+            "allocated {} bytes in {} allocations, over the budget of {} bytes",
+            alloc.bytes,
+            alloc.count,
+            $bytes
+        );
+        value
+    }};
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, measure};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 << 20), "3.0 MiB");
+    }
+
+    #[test]
+    fn measures_nothing_without_the_allocator() {
+        // the lib tests run without the counting allocator installed.
+        let (len, alloc) = measure(|| vec![0u8; 4096].len());
+
+        assert_eq!(len, 4096);
+        assert_eq!(alloc.bytes, 0);
+    }
+}
@@ -0,0 +1,216 @@
+/// Expected answers per input and part, kept in `data/answers.json` rather than in the source of a
+/// solution. Answers are stored as strings, and compared with the `Display` output of a part.
+/// Inputs are keyed by day, e.g. `07`, or by day and variant, e.g. `07.alice`.
+use std::{collections::HashMap, fmt::Display, fs, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::{
+    ocr,
+    template::{error::PartResult, Day},
+};
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    data: HashMap<(Day, Option<String>, u8), String>,
+}
+
+impl Answers {
+    /// Rehydrate answers from a JSON file. If not present, returns no answers.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(ANSWERS_FILE_PATH) {
+            Ok(contents) => Answers::try_from(contents),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), String> {
+        let json = JsonValue::from(self)
+            .format()
+            .map_err(|err| err.to_string())?;
+
+        fs::write(ANSWERS_FILE_PATH, json + "\n").map_err(|err| err.to_string())
+    }
+
+    pub fn get(&self, day: Day, variant: Option<&str>, part: u8) -> Option<&str> {
+        self.data
+            .get(&(day, variant.map(Into::into), part))
+            .map(String::as_str)
+    }
+
+    pub fn set(&mut self, day: Day, variant: Option<&str>, part: u8, answer: &str) {
+        self.data
+            .insert((day, variant.map(Into::into), part), answer.to_string());
+    }
+}
+
+/// The answer a part is expected to give on an input. For the main input, an answer passed to
+/// `solution!` takes precedence over the one in `data/answers.json`.
+pub fn expected<T: Display>(
+    day: Day,
+    variant: Option<&str>,
+    part: u8,
+    literal: Option<T>,
+) -> Result<Option<String>, String> {
+    match (variant, literal) {
+        (None, Some(literal)) => Ok(Some(literal.to_string())),
+        _ => Ok(Answers::read_from_file()?
+            .get(day, variant, part)
+            .map(Into::into)),
+    }
+}
+
+/// Whether an answer is the expected one. An answer drawn in block letters also matches the
+/// letters it reads as.
+pub fn matches(answer: &str, expected: &str) -> bool {
+    answer == expected
+        || (answer.contains('\n') && ocr::parse(answer).is_some_and(|letters| letters == expected))
+}
+
+/// Stores the correct answer of a part on the main input in `data/answers.json`.
+pub fn record(day: Day, part: u8, answer: &str) -> Result<(), String> {
+    let mut answers = Answers::read_from_file()?;
+    answers.set(day, None, part, answer);
+    answers.store_file()
+}
+
+/// Asserts that a part gives its expected answer, for the tests generated by `solution!`. The part
+/// is only solved if there is an answer to check it against.
+#[track_caller]
+pub fn check<R: PartResult>(
+    day: Day,
+    part: u8,
+    literal: Option<R::Answer>,
+    solve: impl FnOnce() -> R,
+) {
+    let expected = expected(day, None, part, literal)
+        .unwrap_or_else(|err| panic!("invalid answers file: {err}"));
+
+    if let Some(expected) = expected {
+        let result = match solve().answer() {
+            Ok(result) => result.map(ToString::to_string),
+            Err(err) => panic!("day {day}, part {part} failed: {err}"),
+        };
+        if !result
+            .as_ref()
+            .is_some_and(|result| matches(result, &expected))
+        {
+            assert_eq!(
+                result,
+                Some(expected),
+                "wrong answer for day {day}, part {part}"
+            );
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for ((day, variant, part), answer) in &value.data {
+            let key = match variant {
+                Some(variant) => format!("{day}.{variant}"),
+                None => day.to_string(),
+            };
+
+            let day = map
+                .entry(key)
+                .or_insert_with(|| JsonValue::Object(HashMap::new()));
+
+            if let JsonValue::Object(parts) = day {
+                parts.insert(format!("part_{part}"), JsonValue::String(answer.clone()));
+            }
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let days = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object keyed by day.")?;
+
+        let mut answers = Answers::default();
+
+        for (input, parts) in days {
+            let (day, variant) = match input.split_once('.') {
+                Some((day, variant)) => (day, Some(variant)),
+                None => (input.as_str(), None),
+            };
+            let day = Day::from_str(day).map_err(|_| format!("invalid input: {input}"))?;
+
+            let parts = parts
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or(format!("expected answers of {input} to be an object."))?;
+
+            for (key, answer) in parts {
+                let part = match key.as_str() {
+                    "part_1" => 1,
+                    "part_2" => 2,
+                    _ => return Err(format!("unknown part {key} for {input}.")),
+                };
+
+                let answer = answer
+                    .get::<String>()
+                    .ok_or(format!("expected answers.{input}.{key} to be a string."))?;
+
+                answers.set(day, variant, part, answer);
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::Answers;
+    use crate::day;
+
+    #[test]
+    fn reads_answers() {
+        let json = r#"{ "04": { "part_1": "2514", "part_2": "1888" }, "04.alice": { "part_1": "2400" }, "05": {} }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+
+        assert_eq!(answers.get(day!(4), None, 1), Some("2514"));
+        assert_eq!(answers.get(day!(4), None, 2), Some("1888"));
+        assert_eq!(answers.get(day!(4), Some("alice"), 1), Some("2400"));
+        assert_eq!(answers.get(day!(4), Some("alice"), 2), None);
+        assert_eq!(answers.get(day!(5), None, 1), None);
+    }
+
+    #[test]
+    fn rejects_malformed_answers() {
+        assert!(Answers::try_from(r#"{ "04": { "part_3": "1" } }"#.to_string()).is_err());
+        assert!(Answers::try_from(r#"{ "04": { "part_1": 2514 } }"#.to_string()).is_err());
+        assert!(Answers::try_from(r#"{ "26": {} }"#.to_string()).is_err());
+    }
+
+    #[test]
+    fn round_trips_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), None, 1, "42");
+        answers.set(day!(1), None, 2, "multi\nline");
+        answers.set(day!(1), Some("alice"), 1, "41");
+        answers.set(day!(12), None, 2, "ABCD");
+
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json), Ok(answers));
+    }
+}
@@ -1,11 +1,15 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
+    ffi::OsStr,
     fmt::Display,
     process::{Command, Output, Stdio},
 };
 
 use crate::template::Day;
 
+/// The aoc-cli binary, as looked up on the `PATH`.
+pub const PROGRAM: &str = "aoc";
+
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
@@ -26,7 +30,7 @@ impl Display for AocCommandError {
 }
 
 pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
+    Command::new(PROGRAM)
         .arg("-V")
         .output()
         .map_err(|_| AocCommandError::CommandNotFound)?;
@@ -72,12 +76,19 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Submits an answer through the aoc-cli binary `program`. The output of aoc-cli is captured rather
+/// than printed, so that the verdict can be read from the response.
+pub fn submit(
+    program: impl AsRef<OsStr>,
+    day: Day,
+    part: u8,
+    result: &str,
+) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+    call_aoc_cli_with(program, &args, Stdio::piped())
 }
 
 fn get_input_path(day: Day) -> String {
@@ -109,10 +120,18 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
 }
 
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+    call_aoc_cli_with(PROGRAM, args, Stdio::inherit())
+}
+
+fn call_aoc_cli_with(
+    program: impl AsRef<OsStr>,
+    args: &[String],
+    stdout: Stdio,
+) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
+    let output = Command::new(program)
         .args(args)
-        .stdout(Stdio::inherit())
+        .stdout(stdout)
         .stderr(Stdio::inherit())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
@@ -0,0 +1,222 @@
+/// Limits on how long and how often a solution part is run when benching.
+use std::{env, time::Duration};
+
+const DEFAULT_BENCH_TIME: Duration = Duration::from_secs(1);
+const DEFAULT_MIN_SAMPLES: u128 = 10;
+const DEFAULT_MAX_SAMPLES: u128 = 10_000;
+
+const BENCH_TIME_VAR: &str = "AOC_BENCH_TIME";
+const MIN_SAMPLES_VAR: &str = "AOC_MIN_SAMPLES";
+const MAX_SAMPLES_VAR: &str = "AOC_MAX_SAMPLES";
+
+/// Set through the `--bench-time`, `--min-samples` and `--max-samples` options, or the
+/// `AOC_BENCH_TIME`, `AOC_MIN_SAMPLES` and `AOC_MAX_SAMPLES` env vars. Options left unset fall back
+/// to a one second budget of between 10 and 10.000 samples.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BenchOptions {
+    pub time: Option<Duration>,
+    pub min_samples: Option<u128>,
+    pub max_samples: Option<u128>,
+}
+
+impl BenchOptions {
+    /// Reads the options a solution binary was invoked with, falling back to the env vars.
+    pub fn from_env() -> Result<Self, String> {
+        let args: Vec<String> = env::args().collect();
+        let var = |name| env::var(name).ok();
+
+        let from_vars = Self::parse(
+            var(BENCH_TIME_VAR).as_deref(),
+            var(MIN_SAMPLES_VAR).as_deref(),
+            var(MAX_SAMPLES_VAR).as_deref(),
+        )?;
+
+        Ok(Self::from_args(&args)?.or(from_vars))
+    }
+
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .map(|index| {
+                    args.get(index + 1)
+                        .map(String::as_str)
+                        .ok_or(format!("missing value for {flag}"))
+                })
+                .transpose()
+        };
+
+        Self::parse(
+            value("--bench-time")?,
+            value("--min-samples")?,
+            value("--max-samples")?,
+        )
+    }
+
+    fn parse(
+        time: Option<&str>,
+        min_samples: Option<&str>,
+        max_samples: Option<&str>,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            time: time.map(parse_duration).transpose()?,
+            min_samples: min_samples.map(parse_samples).transpose()?,
+            max_samples: max_samples.map(parse_samples).transpose()?,
+        })
+    }
+
+    /// Combines two sets of options, preferring the ones set in `self`.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            time: self.time.or(other.time),
+            min_samples: self.min_samples.or(other.min_samples),
+            max_samples: self.max_samples.or(other.max_samples),
+        }
+    }
+
+    /// The options as command-line arguments, to pass them on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(time) = self.time {
+            args.extend(["--bench-time".into(), format!("{}ns", time.as_nanos())]);
+        }
+
+        if let Some(min_samples) = self.min_samples {
+            args.extend(["--min-samples".into(), min_samples.to_string()]);
+        }
+
+        if let Some(max_samples) = self.max_samples {
+            args.extend(["--max-samples".into(), max_samples.to_string()]);
+        }
+
+        args
+    }
+
+    pub fn time(&self) -> Duration {
+        self.time.unwrap_or(DEFAULT_BENCH_TIME)
+    }
+
+    /// The bounds on the number of samples taken. A bound that is not set gives way to one that
+    /// is, so only two bounds that are both set can conflict.
+    pub fn samples(&self) -> Result<(u128, u128), String> {
+        let max = self
+            .max_samples
+            .unwrap_or(DEFAULT_MAX_SAMPLES.max(self.min_samples.unwrap_or(0)));
+        let min = self.min_samples.unwrap_or(DEFAULT_MIN_SAMPLES.min(max));
+
+        if min > max {
+            return Err(format!(
+                "minimum samples ({min}) exceed maximum samples ({max})"
+            ));
+        }
+
+        Ok((min, max))
+    }
+}
+
+/// Parses a duration like `100ms` or `10s`. Durations without a unit are taken as milliseconds.
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+
+    let amount: f64 = amount
+        .parse()
+        .map_err(|_| format!("invalid duration: {value}"))?;
+
+    let seconds = match unit.trim() {
+        "ns" => amount / 1e9,
+        "us" | "µs" => amount / 1e6,
+        "" | "ms" => amount / 1e3,
+        "s" => amount,
+        "m" | "min" => amount * 60.0,
+        _ => return Err(format!("invalid duration unit: {value}")),
+    };
+
+    Duration::try_from_secs_f64(seconds).map_err(|_| format!("invalid duration: {value}"))
+}
+
+pub fn parse_samples(value: &str) -> Result<u128, String> {
+    match value.parse() {
+        Ok(0) | Err(_) => Err(format!("invalid number of samples: {value}")),
+        Ok(samples) => Ok(samples),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse_duration, BenchOptions};
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("100ms"), Ok(Duration::from_millis(100)));
+        assert_eq!(parse_duration("10s"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("250"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("50µs"), Ok(Duration::from_micros(50)));
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("10h").is_err());
+    }
+
+    #[test]
+    fn round_trips_args() {
+        let options = BenchOptions {
+            time: Some(Duration::from_millis(100)),
+            min_samples: None,
+            max_samples: Some(50),
+        };
+
+        let mut args = vec!["target/release/01".to_string(), "--time".to_string()];
+        args.extend(options.to_args());
+
+        assert_eq!(BenchOptions::from_args(&args), Ok(options));
+    }
+
+    #[test]
+    fn prefers_args_over_fallback() {
+        let args = ["--min-samples".to_string(), "3".to_string()];
+        let fallback = BenchOptions {
+            time: Some(Duration::from_secs(10)),
+            min_samples: Some(100),
+            max_samples: None,
+        };
+
+        let options = BenchOptions::from_args(&args).unwrap().or(fallback);
+
+        assert_eq!(options.time(), Duration::from_secs(10));
+        assert_eq!(options.samples(), Ok((3, 10_000)));
+    }
+
+    #[test]
+    fn rejects_invalid_sample_bounds() {
+        let args = ["--min-samples".to_string(), "0".to_string()];
+        assert!(BenchOptions::from_args(&args).is_err());
+
+        let options = BenchOptions {
+            min_samples: Some(100),
+            max_samples: Some(10),
+            ..BenchOptions::default()
+        };
+        assert!(options.samples().is_err());
+    }
+
+    #[test]
+    fn fits_default_sample_bounds_to_set_ones() {
+        let args = ["--max-samples".to_string(), "5".to_string()];
+        assert_eq!(
+            BenchOptions::from_args(&args).unwrap().samples(),
+            Ok((5, 5))
+        );
+
+        let args = ["--min-samples".to_string(), "20000".to_string()];
+        assert_eq!(
+            BenchOptions::from_args(&args).unwrap().samples(),
+            Ok((20_000, 20_000))
+        );
+    }
+}
@@ -1,5 +1,21 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{
+    all_days, bench_options::BenchOptions, limits::Limits, run_multi::run_multi,
+};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(
+    is_release: bool,
+    alloc_stats: bool,
+    bench_options: &BenchOptions,
+    limits: &Limits,
+    is_json: bool,
+) {
+    run_multi(
+        &all_days().collect(),
+        is_release,
+        false,
+        alloc_stats,
+        bench_options,
+        limits,
+        is_json,
+    );
 }
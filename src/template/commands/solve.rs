@@ -1,8 +1,39 @@
-use std::process::{Command, Stdio};
+use std::{
+    iter,
+    process::{self, Command},
+    time::Duration,
+};
 
-use crate::template::Day;
+use crate::template::{
+    bench_options::BenchOptions,
+    input_variants,
+    limits::Limits,
+    report::{self, Report},
+    run_multi::{child_commands::collect_reports, is_interactive, say},
+    Day, ANSI_BOLD, ANSI_RESET,
+};
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    alloc_stats: bool,
+    submit_part: Option<u8>,
+    all_inputs: bool,
+    input: Option<&str>,
+    visualize: bool,
+    viz_delay: Option<Duration>,
+    verbosity: u8,
+    bench_options: &BenchOptions,
+    limits: &Limits,
+    is_json: bool,
+) {
+    if all_inputs && input.is_some() {
+        eprintln!("--input cannot be combined with --all-inputs.");
+        process::exit(1);
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -16,19 +47,143 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if alloc_stats {
+        cmd_args.extend(["--features".to_string(), "alloc-stats".to_string()]);
+    }
+
+    if visualize {
+        cmd_args.extend(["--features".to_string(), "viz".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
+    cmd_args.extend(bench_options.to_args());
+    let limits = limits.with_day(day).unwrap_or_else(|err| {
+        eprintln!("Invalid limits for day {day}: {err}");
+        process::exit(1);
+    });
+    cmd_args.extend(limits.to_args());
+
+    // the child reads the input itself, which for `-` is the stdin it inherits.
+    if let Some(input) = input {
+        cmd_args.extend(["--input".to_string(), input.to_string()]);
+    }
+
+    if visualize {
+        cmd_args.push("--visualize".to_string());
+    }
+
+    if let Some(delay) = viz_delay {
+        cmd_args.extend(["--viz-delay".to_string(), format!("{}ns", delay.as_nanos())]);
+    }
+
+    if verbosity > 0 {
+        if release || dhat {
+            eprintln!(
+                "Debug output is compiled out of release builds; run without --release to see it."
+            );
+        }
+        cmd_args.push(format!("-{}", "v".repeat(verbosity.into())));
+    }
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+    // the output of the child is piped, so it is told when it can draw progress bars.
+    if is_interactive(is_json) {
+        cmd_args.push("--progress".to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+    // the child reports as JSON so that steps which hit a limit are reported as such.
+    cmd_args.push("--json".to_string());
+
+    // the main input comes first, followed by its variants, e.g. `07.alice.txt`.
+    let inputs: Vec<Option<String>> = if all_inputs {
+        iter::once(None)
+            .chain(input_variants(day).into_iter().map(Some))
+            .collect()
+    } else {
+        vec![None]
+    };
+
+    let mut runs: Vec<(Day, Vec<Report>)> = vec![];
+
+    for (index, variant) in inputs.iter().enumerate() {
+        let mut cmd = Command::new("cargo");
+        cmd.args(&cmd_args);
+
+        match variant {
+            Some(variant) => {
+                cmd.args(["--variant", variant]);
+            }
+            // only answers for the main input are submitted.
+            None => {
+                if let Some(submit_part) = submit_part {
+                    cmd.args(["--submit".to_string(), submit_part.to_string()]);
+                }
+            }
+        }
 
-    cmd.wait().unwrap();
+        if all_inputs {
+            if index > 0 {
+                say(is_json, "");
+            }
+            say(
+                is_json,
+                &format!("{ANSI_BOLD}Input {}{ANSI_RESET}", input_name(day, variant)),
+            );
+        }
+
+        runs.push((day, collect_reports(cmd, is_json).unwrap()));
+    }
+
+    if all_inputs {
+        print_mismatches(day, &inputs, &runs, is_json);
+    }
+
+    if is_json {
+        println!("{}", report::summarize(&runs).stringify().unwrap());
+    }
+}
+
+fn input_name(day: Day, variant: &Option<String>) -> String {
+    match variant {
+        Some(variant) => format!("{day}.{variant}"),
+        None => day.to_string(),
+    }
+}
+
+/// Flags the inputs on which a part gave another answer than the expected one.
+fn print_mismatches(
+    day: Day,
+    inputs: &[Option<String>],
+    runs: &[(Day, Vec<Report>)],
+    is_json: bool,
+) {
+    let mismatches: Vec<String> = inputs
+        .iter()
+        .zip(runs)
+        .flat_map(|(variant, (_, reports))| {
+            reports
+                .iter()
+                .filter(|report| report.correct == Some(false))
+                .map(move |report| format!("{} ({})", input_name(day, variant), report.step))
+        })
+        .collect();
+
+    let checked = runs
+        .iter()
+        .flat_map(|(_, reports)| reports)
+        .any(|report| report.correct.is_some());
+
+    if !checked {
+        return;
+    }
+
+    say(is_json, "");
+
+    if mismatches.is_empty() {
+        say(is_json, "✅ All inputs gave their expected answers.");
+    } else {
+        say(
+            is_json,
+            &format!("❌ Unexpected answers on {}", mismatches.join(", ")),
+        );
+    }
 }
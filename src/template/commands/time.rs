@@ -1,10 +1,20 @@
 use std::collections::HashSet;
 
-use crate::template::run_multi::run_multi;
+use crate::template::bench_options::BenchOptions;
+use crate::template::limits::Limits;
+use crate::template::run_multi::{run_multi, say};
 use crate::template::timings::Timings;
 use crate::template::{all_days, readme_benchmarks, Day};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    alloc_stats: bool,
+    bench_options: &BenchOptions,
+    limits: &Limits,
+    is_json: bool,
+) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -21,16 +31,25 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(
+        &days_to_run,
+        true,
+        true,
+        alloc_stats,
+        bench_options,
+        limits,
+        is_json,
+    )
+    .unwrap();
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
-        println!();
+        say(is_json, "");
         match readme_benchmarks::update(merged_timings) {
             Ok(()) => {
-                println!("Stored updated benchmarks.");
+                say(is_json, "Stored updated benchmarks.");
             }
             Err(_) => {
                 eprintln!("Failed to store updated benchmarks.");
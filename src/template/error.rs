@@ -0,0 +1,102 @@
+/// An error for solution parts to return, which any other error converts into with `?`:
+///
+/// ```ignore
+/// pub fn part_one(input: &str) -> Result<u32, advent_of_code::Error> {
+///     let depth: u32 = input.trim().parse()?;
+///     let guard = grid.find(&'^').ok_or(Error::new("no guard on the map"))?;
+///     // ...
+/// }
+/// ```
+use std::fmt::Display;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error(String);
+
+impl Error {
+    pub fn new(message: impl Display) -> Self {
+        Self(message.to_string())
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// `Error` itself cannot implement `std::error::Error`, as this would conflict with `From<T> for T`.
+impl<E: std::error::Error> From<E> for Error {
+    fn from(err: E) -> Self {
+        Self::new(err)
+    }
+}
+
+/// What a solution part returns: an `Option` of its answer, or a `Result` of it. The error of a
+/// failed part is reported in place of an answer.
+pub trait PartResult {
+    type Answer: Display;
+
+    /// The answer of the part, or what it failed with.
+    fn answer(&self) -> Result<Option<&Self::Answer>, String>;
+
+    /// The answer of the part, if any, e.g. to hand on to part two of a chained solution.
+    fn into_answer(self) -> Option<Self::Answer>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
+
+    fn answer(&self) -> Result<Option<&T>, String> {
+        Ok(self.as_ref())
+    }
+
+    fn into_answer(self) -> Option<T> {
+        self
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    type Answer = T;
+
+    fn answer(&self) -> Result<Option<&T>, String> {
+        match self {
+            Ok(answer) => Ok(Some(answer)),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    fn into_answer(self) -> Option<T> {
+        self.ok()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Error, PartResult};
+
+    fn parse(input: &str) -> Result<u32, Error> {
+        Ok(input.trim().parse::<u32>()?)
+    }
+
+    #[test]
+    fn converts_errors() {
+        assert_eq!(parse("42\n"), Ok(42));
+        assert_eq!(
+            parse("4x2").map_err(|err| err.to_string()),
+            Err("invalid digit found in string".into())
+        );
+        assert_eq!(Error::new("no guard").to_string(), "no guard");
+    }
+
+    #[test]
+    fn reads_part_results() {
+        assert_eq!(Some(42).answer(), Ok(Some(&42)));
+        assert_eq!(None::<u32>.answer(), Ok(None));
+        assert_eq!(Ok::<u32, Error>(42).answer(), Ok(Some(&42)));
+        assert_eq!(
+            Err::<u32, _>(Error::new("no guard")).answer(),
+            Err("no guard".into())
+        );
+        assert_eq!(Err::<u32, _>("no guard").into_answer(), None);
+    }
+}
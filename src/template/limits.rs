@@ -0,0 +1,247 @@
+/// Wall-clock and memory limits for solution parts, so that a runaway solution does not block a
+/// run of several days.
+use std::{collections::HashMap, env, fs, str::FromStr, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::{bench_options::parse_duration, Day};
+
+static LIMITS_FILE_PATH: &str = "./data/limits.json";
+
+const TIMEOUT_VAR: &str = "AOC_TIMEOUT";
+const MEMORY_LIMIT_VAR: &str = "AOC_MEMORY_LIMIT";
+
+/// Set through the `--timeout` and `--memory-limit` options, per day in `data/limits.json`, or
+/// through the `AOC_TIMEOUT` and `AOC_MEMORY_LIMIT` env vars, in that order of precedence.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Wall-clock time the first run of each part may take. Benching stops taking samples before it
+    /// runs past the same time.
+    pub timeout: Option<Duration>,
+    /// Size of the address space a solution binary may use, in bytes.
+    pub memory: Option<u64>,
+}
+
+impl Limits {
+    /// Reads the limits a solution binary was invoked with, falling back to the env vars.
+    pub fn from_env() -> Result<Self, String> {
+        let args: Vec<String> = env::args().collect();
+        let var = |name| env::var(name).ok();
+
+        let from_vars = Self::parse(
+            var(TIMEOUT_VAR).as_deref(),
+            var(MEMORY_LIMIT_VAR).as_deref(),
+        )?;
+
+        Ok(Self::from_args(&args)?.or(from_vars))
+    }
+
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .map(|index| {
+                    args.get(index + 1)
+                        .map(String::as_str)
+                        .ok_or(format!("missing value for {flag}"))
+                })
+                .transpose()
+        };
+
+        Self::parse(value("--timeout")?, value("--memory-limit")?)
+    }
+
+    fn parse(timeout: Option<&str>, memory: Option<&str>) -> Result<Self, String> {
+        Ok(Self {
+            timeout: timeout.map(parse_duration).transpose()?,
+            memory: memory.map(parse_bytes).transpose()?,
+        })
+    }
+
+    /// Reads the limits configured for a day in `data/limits.json`. If not present, returns no
+    /// limits.
+    pub fn for_day(day: Day) -> Result<Self, String> {
+        match fs::read_to_string(LIMITS_FILE_PATH) {
+            Ok(contents) => Self::from_file_contents(&contents, day),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Combines the limits given for a run with the ones configured for `day`, preferring the
+    /// former.
+    pub fn with_day(&self, day: Day) -> Result<Self, String> {
+        Ok(self.clone().or(Self::for_day(day)?))
+    }
+
+    fn from_file_contents(contents: &str, day: Day) -> Result<Self, String> {
+        let json = JsonValue::from_str(contents).or(Err("limits file is not valid JSON."))?;
+
+        let days = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected limits file to be an object keyed by day.")?;
+
+        let Some(limits) = days.get(&day.to_string()) else {
+            return Ok(Self::default());
+        };
+
+        let limits = limits
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or(format!("expected limits of day {day} to be an object."))?;
+
+        let value = |key: &str| match limits.get(key) {
+            None => Ok(None),
+            Some(JsonValue::String(value)) => Ok(Some(value.as_str())),
+            Some(_) => Err(format!("expected limits.{day}.{key} to be a string.")),
+        };
+
+        Self::parse(value("timeout")?, value("memory_limit")?)
+    }
+
+    /// Combines two sets of limits, preferring the ones set in `self`.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            timeout: self.timeout.or(other.timeout),
+            memory: self.memory.or(other.memory),
+        }
+    }
+
+    /// The limits as command-line arguments, to pass them on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(timeout) = self.timeout {
+            args.extend(["--timeout".into(), format!("{}ns", timeout.as_nanos())]);
+        }
+
+        if let Some(memory) = self.memory {
+            args.extend(["--memory-limit".into(), memory.to_string()]);
+        }
+
+        args
+    }
+
+    /// Caps the address space of the current process to the memory limit, if one is set.
+    /// Allocations past the limit then fail and abort the process.
+    pub fn apply_memory_limit(&self) -> Result<(), String> {
+        let Some(memory) = self.memory else {
+            return Ok(());
+        };
+
+        set_address_space_limit(memory)
+    }
+}
+
+#[cfg(unix)]
+fn set_address_space_limit(bytes: u64) -> Result<(), String> {
+    let os_error = |action| format!("could not {action}: {}", std::io::Error::last_os_error());
+
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+
+    // SAFETY: `limit` is a valid rlimit that outlives the call.
+    if unsafe { libc::getrlimit(libc::RLIMIT_AS, &mut limit) } != 0 {
+        return Err(os_error("read memory limit"));
+    }
+
+    // the hard limit can only be lowered, so keep it and stay below it
+    let bytes = bytes as libc::rlim_t;
+    if limit.rlim_max != libc::RLIM_INFINITY && bytes > limit.rlim_max {
+        return Err(format!(
+            "memory limit of {bytes} bytes is above the hard limit of {} bytes.",
+            limit.rlim_max
+        ));
+    }
+
+    limit.rlim_cur = bytes;
+
+    // SAFETY: `limit` is a valid rlimit that outlives the call.
+    match unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) } {
+        0 => Ok(()),
+        _ => Err(os_error("limit memory")),
+    }
+}
+
+#[cfg(not(unix))]
+fn set_address_space_limit(_: u64) -> Result<(), String> {
+    Err("memory limits are only supported on unix.".into())
+}
+
+/// Parses a number of bytes like `512M` or `2GiB`. Units are powers of 1024.
+pub fn parse_bytes(value: &str) -> Result<u64, String> {
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("invalid number of bytes: {value}"))?;
+
+    let multiplier: u64 = match unit.trim().trim_end_matches("iB").trim_end_matches('B') {
+        "" => 1,
+        "K" | "k" => 1 << 10,
+        "M" => 1 << 20,
+        "G" => 1 << 30,
+        _ => return Err(format!("invalid unit of bytes: {value}")),
+    };
+
+    amount
+        .checked_mul(multiplier)
+        .ok_or(format!("number of bytes is too large: {value}"))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse_bytes, Limits};
+    use crate::day;
+
+    #[test]
+    fn parses_bytes() {
+        assert_eq!(parse_bytes("4096"), Ok(4096));
+        assert_eq!(parse_bytes("512M"), Ok(512 << 20));
+        assert_eq!(parse_bytes("2GiB"), Ok(2 << 30));
+        assert_eq!(parse_bytes("64KB"), Ok(64 << 10));
+        assert!(parse_bytes("lots").is_err());
+        assert!(parse_bytes("1T").is_err());
+    }
+
+    #[test]
+    fn reads_limits_per_day() {
+        let contents = r#"{ "06": { "timeout": "10s", "memory_limit": "1G" }, "07": {} }"#;
+
+        assert_eq!(
+            Limits::from_file_contents(contents, day!(6)),
+            Ok(Limits {
+                timeout: Some(Duration::from_secs(10)),
+                memory: Some(1 << 30),
+            })
+        );
+        assert_eq!(
+            Limits::from_file_contents(contents, day!(7)),
+            Ok(Limits::default())
+        );
+        assert_eq!(
+            Limits::from_file_contents(contents, day!(8)),
+            Ok(Limits::default())
+        );
+        assert!(Limits::from_file_contents(r#"{ "06": { "timeout": 10 } }"#, day!(6)).is_err());
+    }
+
+    #[test]
+    fn round_trips_args() {
+        let limits = Limits {
+            timeout: Some(Duration::from_millis(1500)),
+            memory: Some(256 << 20),
+        };
+
+        let mut args = vec!["target/release/06".to_string(), "--json".to_string()];
+        args.extend(limits.to_args());
+
+        assert_eq!(Limits::from_args(&args), Ok(limits));
+    }
+}
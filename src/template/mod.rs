@@ -1,14 +1,26 @@
-use std::{env, fs};
+use std::{env, fs, path::Path};
 
+pub mod alloc_stats;
+pub mod answers;
 pub mod aoc_cli;
+pub mod bench_options;
 pub mod commands;
+pub mod error;
+pub mod limits;
+pub mod progress;
 pub mod runner;
+pub mod verbosity;
+pub mod viz;
 
 pub use day::*;
 
 mod day;
+mod perf_counters;
 mod readme_benchmarks;
+mod report;
 mod run_multi;
+mod stats;
+mod submissions;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -20,8 +32,8 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let f = fs::read_to_string(&filepath);
+    f.unwrap_or_else(|err| panic!("could not open input file {}: {err}", filepath.display()))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
@@ -32,50 +44,219 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
         .join("data")
         .join(folder)
         .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let f = fs::read_to_string(&filepath);
+    f.unwrap_or_else(|err| panic!("could not open input file {}: {err}", filepath.display()))
+}
+
+/// Helper function that reads a variant of a text file to string, e.g. `07.alice.txt` for variant
+/// `alice`.
+#[must_use]
+pub fn read_file_variant(folder: &str, day: Day, variant: &str) -> String {
+    let cwd = env::current_dir().unwrap();
+    let filepath = cwd
+        .join("data")
+        .join(folder)
+        .join(format!("{day}.{variant}.txt"));
+    let f = fs::read_to_string(&filepath);
+    f.unwrap_or_else(|err| panic!("could not open input file {}: {err}", filepath.display()))
+}
+
+/// Lists the names of the variants of a day's input, e.g. `alice` for `data/inputs/07.alice.txt`.
+#[must_use]
+pub fn input_variants(day: Day) -> Vec<String> {
+    let prefix = format!("{day}.");
+
+    let mut variants: Vec<String> = fs::read_dir(Path::new("data").join("inputs"))
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let variant = name.strip_prefix(&prefix)?.strip_suffix(".txt")?;
+            (!variant.is_empty()).then(|| variant.to_string())
+        })
+        .collect();
+
+    variants.sort_unstable();
+    variants
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Passing `parse = <fn>` runs the given `fn(&str) -> Input` once, and hands `&Input` to both parts.
+/// Parsing is then timed separately from solving.
+///
+/// Passing `chain` hands part one's answer and state to part two. `part_one` then returns
+/// `(Option<T>, State)` and `part_two` takes `(input, Option<&T>, &State)`. `chain, 2` runs part two
+/// alone, timing it together with the part one it depends on.
+///
+/// Parts return an `Option` of their answer, or a `Result` of it whose error is reported instead,
+/// e.g. `Result<u32, advent_of_code::Error>`. With `chain`, part two receives part one's answer as
+/// an `Option` either way.
+///
+/// Expected answers are read from `data/answers.json`, unless passed as the last two parameters.
+/// Both `solve` and the generated `solution_tests` check the parts against them.
 #[macro_export]
 macro_rules! solution {
+    ($day:expr, $(parse = $parse:path,)? chain) => {
+        $crate::solution!(@impl_chained $day, [$($parse)?], None, None);
+        $crate::solution!(@tests_chained [$($parse)?], None, None);
+    };
+    ($day:expr, $(parse = $parse:path,)? chain, 2) => {
+        $crate::solution!(@setup $day);
+        $crate::solution!(@tests_chained [$($parse)?], None, None);
+
+        fn main() {
+            use $crate::template::runner::*;
+            let input = read_input(DAY);
+            $( let Some(input) = run_parse($parse, &input, DAY) else { return; }; )?
+            let chained = |input| {
+                let (one, state) = part_one(input);
+                let one = $crate::template::error::PartResult::into_answer(one);
+                part_two(input, one.as_ref(), &state)
+            };
+            run_part(chained, &input, DAY, 2, None);
+        }
+    };
+    ($day:expr, $(parse = $parse:path,)? chain, $expected_one:expr, $expected_two:expr) => {
+        $crate::solution!(@impl_chained $day, [$($parse)?], $expected_one, $expected_two);
+        $crate::solution!(@tests_chained [$($parse)?], $expected_one, $expected_two);
+    };
     ($day:expr) => {
         $crate::solution!(@impl $day, [part_one, 1, None] [part_two, 2, None]);
+        $crate::solution!(@tests [part_one, 1, None, test_part_one] [part_two, 2, None, test_part_two]);
     };
     ($day:expr, 1) => {
         $crate::solution!(@impl $day, [part_one, 1, None]);
+        $crate::solution!(@tests [part_one, 1, None, test_part_one]);
     };
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2, None]);
+        $crate::solution!(@tests [part_two, 2, None, test_part_two]);
+    };
+    ($day:expr, parse = $parse:path) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1, None] [part_two, 2, None]);
+        $crate::solution!(@tests_parsed $parse, [part_one, 1, None, test_part_one] [part_two, 2, None, test_part_two]);
+    };
+    ($day:expr, parse = $parse:path, $expected_one:expr, $expected_two:expr) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1, $expected_one] [part_two, 2, $expected_two]);
+        $crate::solution!(@tests_parsed $parse, [part_one, 1, $expected_one, test_part_one] [part_two, 2, $expected_two, test_part_two]);
     };
     ($day:expr, $expected_one:expr, $expected_two:expr) => {
         $crate::solution!(@impl $day, [part_one, 1, $expected_one] [part_two, 2, $expected_two]);
+        $crate::solution!(@tests [part_one, 1, $expected_one, test_part_one] [part_two, 2, $expected_two, test_part_two]);
+    };
+
+    (@impl $day:expr, $( [$func:expr, $part:expr, $expect:expr] )*) => {
+        $crate::solution!(@setup $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            let input = read_input(DAY);
+            $( run_part($func, &input, DAY, $part, $expect); )*
+        }
+    };
+
+    (@impl_parsed $day:expr, $parse:path, $( [$func:expr, $part:expr, $expect:expr] )*) => {
+        $crate::solution!(@setup $day);
 
+        fn main() {
+            use $crate::template::runner::*;
+            let input = read_input(DAY);
+            let Some(parsed) = run_parse($parse, &input, DAY) else {
+                return;
+            };
+            // call through a closure so that parts may take e.g. a slice of a parsed `Vec`.
+            $( run_part(|input| $func(input), &parsed, DAY, $part, $expect); )*
+        }
+    };
+
+    (@impl_chained $day:expr, [$($parse:path)?], $expected_one:expr, $expected_two:expr) => {
+        $crate::solution!(@setup $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            let input = read_input(DAY);
+            $( let Some(input) = run_parse($parse, &input, DAY) else { return; }; )?
+            // part two cannot run without the state of part one.
+            let Some((one, state)) =
+                run_part_with_state(|input| part_one(input), &input, DAY, 1, $expected_one)
+            else {
+                return;
+            };
+            run_part(
+                |input| part_two(input, one.as_ref(), &state),
+                &input,
+                DAY,
+                2,
+                $expected_two,
+            );
+        }
+    };
+
+    // the generated tests solve the real input, if there is an answer to check it against.
+    (@tests $( [$func:ident, $part:expr, $expect:expr, $test:ident] )*) => {
+        #[cfg(test)]
+        mod solution_tests {
+            use super::*;
+
+            $(
+                #[test]
+                fn $test() {
+                    $crate::template::answers::check(DAY, $part, $expect, || {
+                        $func(&$crate::template::read_file("inputs", DAY))
+                    });
+                }
+            )*
+        }
+    };
+
+    (@tests_parsed $parse:path, $( [$func:ident, $part:expr, $expect:expr, $test:ident] )*) => {
+        #[cfg(test)]
+        mod solution_tests {
+            use super::*;
+
+            $(
+                #[test]
+                fn $test() {
+                    $crate::template::answers::check(DAY, $part, $expect, || {
+                        let input = $parse(&$crate::template::read_file("inputs", DAY));
+                        $func(&input)
+                    });
+                }
+            )*
+        }
+    };
+
+    (@tests_chained [$($parse:path)?], $expected_one:expr, $expected_two:expr) => {
         #[cfg(test)]
         mod solution_tests {
             use super::*;
 
             #[test]
             fn test_part_one() {
-                if let Some(expected) = $expected_one {
-                    let result = part_one(&advent_of_code::template::read_file("inputs", DAY));
-                    assert_eq!(result, Some(expected));
-                }
+                $crate::template::answers::check(DAY, 1, $expected_one, || {
+                    let input = $crate::template::read_file("inputs", DAY);
+                    $( let input = $parse(&input); )?
+                    part_one(&input).0
+                });
             }
 
             #[test]
             fn test_part_two() {
-                if let Some(expected) = $expected_two {
-                    let result = part_two(&advent_of_code::template::read_file("inputs", DAY));
-                    assert_eq!(result, Some(expected));
-                }
+                $crate::template::answers::check(DAY, 2, $expected_two, || {
+                    let input = $crate::template::read_file("inputs", DAY);
+                    $( let input = $parse(&input); )?
+                    let (one, state) = part_one(&input);
+                    let one = $crate::template::error::PartResult::into_answer(one);
+                    part_two(&input, one.as_ref(), &state)
+                });
             }
         }
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr, $expect:expr] )*) => {
+    (@setup $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -83,10 +264,9 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part, $expect); )*
-        }
+        #[cfg(all(feature = "alloc-stats", not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc_stats::CountingAlloc =
+            $crate::template::alloc_stats::CountingAlloc;
     };
 }
@@ -0,0 +1,369 @@
+/// Hardware performance counters for benched solution parts, read through `perf_event_open`.
+/// Only supported on Linux; elsewhere, no counters are collected.
+use std::{collections::HashMap, fmt::Display};
+
+use tinyjson::JsonValue;
+
+/// Hardware events counted while benching a part, averaged per run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PerfStats {
+    pub instructions: u64,
+    pub cycles: u64,
+    /// Not every CPU (or VM) exposes these events.
+    pub branch_misses: Option<u64>,
+    pub cache_misses: Option<u64>,
+}
+
+impl PerfStats {
+    /// Instructions per cycle.
+    pub fn ipc(&self) -> f64 {
+        if self.cycles == 0 {
+            0.0
+        } else {
+            self.instructions as f64 / self.cycles as f64
+        }
+    }
+}
+
+impl Display for PerfStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} instructions · {} cycles",
+            format_count(self.instructions),
+            format_count(self.cycles)
+        )?;
+
+        if let Some(branch_misses) = self.branch_misses {
+            write!(f, " · {} branch misses", format_count(branch_misses))?;
+        }
+
+        if let Some(cache_misses) = self.cache_misses {
+            write!(f, " · {} cache misses", format_count(cache_misses))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats a count with a metric suffix, e.g. `1.5M`.
+fn format_count(count: u64) -> String {
+    match count {
+        0..1_000 => count.to_string(),
+        1_000..1_000_000 => format!("{:.1}K", count as f64 / 1e3),
+        1_000_000..1_000_000_000 => format!("{:.1}M", count as f64 / 1e6),
+        _ => format!("{:.1}G", count as f64 / 1e9),
+    }
+}
+
+/// A group of counters for the current thread. They only count inside `measure`, accumulating
+/// over all runs measured.
+pub struct Counters {
+    group: sys::Group,
+    runs: u64,
+}
+
+impl Counters {
+    /// Opens the counters, or returns `Ok(None)` on platforms without support.
+    pub fn open() -> Result<Option<Self>, String> {
+        Ok(sys::Group::open()?.map(|group| Self { group, runs: 0 }))
+    }
+
+    pub fn measure<T>(&mut self, func: impl FnOnce() -> T) -> T {
+        self.group.enable();
+        let result = func();
+        self.group.disable();
+
+        self.runs += 1;
+        result
+    }
+
+    /// The counts of all measured runs, averaged per run.
+    pub fn read(&self) -> Result<PerfStats, String> {
+        let totals = self.group.read()?;
+        let per_run = |count: u64| count / self.runs.max(1);
+
+        Ok(PerfStats {
+            instructions: per_run(totals.instructions),
+            cycles: per_run(totals.cycles),
+            branch_misses: totals.branch_misses.map(per_run),
+            cache_misses: totals.cache_misses.map(per_run),
+        })
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use std::{
+        fs, io,
+        os::fd::{AsRawFd, FromRawFd, OwnedFd},
+    };
+
+    use super::PerfStats;
+
+    const PERF_TYPE_HARDWARE: u32 = 0;
+
+    const PERF_COUNT_HW_CPU_CYCLES: u64 = 0;
+    const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+    const PERF_COUNT_HW_CACHE_MISSES: u64 = 3;
+    const PERF_COUNT_HW_BRANCH_MISSES: u64 = 5;
+
+    const PERF_FORMAT_GROUP: u64 = 1 << 3;
+
+    // bits of the `perf_event_attr` flags bitfield.
+    const ATTR_DISABLED: u64 = 1 << 0;
+    const ATTR_EXCLUDE_KERNEL: u64 = 1 << 5;
+    const ATTR_EXCLUDE_HV: u64 = 1 << 6;
+
+    const PERF_EVENT_IOC_ENABLE: u64 = 0x2400;
+    const PERF_EVENT_IOC_DISABLE: u64 = 0x2401;
+    const PERF_IOC_FLAG_GROUP: u64 = 1;
+
+    const PERF_FLAG_FD_CLOEXEC: libc::c_ulong = 1 << 3;
+
+    /// The first version of `perf_event_attr`, which every kernel since 2.6.31 accepts.
+    #[repr(C)]
+    #[derive(Default)]
+    struct PerfEventAttr {
+        kind: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+    }
+
+    pub struct Group {
+        leader: OwnedFd,
+        /// All events of the group, in the order the kernel reports them.
+        events: Vec<(u64, OwnedFd)>,
+    }
+
+    impl Group {
+        pub fn open() -> Result<Option<Self>, String> {
+            let leader = open_event(PERF_COUNT_HW_CPU_CYCLES, None).map_err(describe_error)?;
+            let mut events = vec![];
+
+            for config in [
+                PERF_COUNT_HW_INSTRUCTIONS,
+                PERF_COUNT_HW_BRANCH_MISSES,
+                PERF_COUNT_HW_CACHE_MISSES,
+            ] {
+                match open_event(config, Some(&leader)) {
+                    Ok(fd) => events.push((config, fd)),
+                    Err(_) if config != PERF_COUNT_HW_INSTRUCTIONS => {}
+                    Err(err) => return Err(describe_error(err)),
+                }
+            }
+
+            Ok(Some(Self { leader, events }))
+        }
+
+        pub fn enable(&self) {
+            self.ioctl(PERF_EVENT_IOC_ENABLE);
+        }
+
+        pub fn disable(&self) {
+            self.ioctl(PERF_EVENT_IOC_DISABLE);
+        }
+
+        fn ioctl(&self, request: u64) {
+            // SAFETY: the fd is an open perf event and the requests take an integer argument.
+            unsafe {
+                libc::ioctl(self.leader.as_raw_fd(), request as _, PERF_IOC_FLAG_GROUP);
+            }
+        }
+
+        pub fn read(&self) -> Result<PerfStats, String> {
+            // the number of events, followed by the value of each, leader first.
+            let mut values = vec![0_u64; self.events.len() + 2];
+            let size = std::mem::size_of_val(values.as_slice());
+
+            // SAFETY: `values` is a valid buffer of `size` bytes.
+            let read =
+                unsafe { libc::read(self.leader.as_raw_fd(), values.as_mut_ptr().cast(), size) };
+
+            if read < 0 {
+                return Err(format!(
+                    "could not read hardware counters: {}",
+                    io::Error::last_os_error()
+                ));
+            }
+
+            let value = |config| {
+                self.events
+                    .iter()
+                    .position(|(event, _)| *event == config)
+                    .map(|index| values[index + 2])
+            };
+
+            Ok(PerfStats {
+                cycles: values[1],
+                instructions: value(PERF_COUNT_HW_INSTRUCTIONS).unwrap_or_default(),
+                branch_misses: value(PERF_COUNT_HW_BRANCH_MISSES),
+                cache_misses: value(PERF_COUNT_HW_CACHE_MISSES),
+            })
+        }
+    }
+
+    /// Opens a counter of the current thread. Only user space is counted, which unprivileged
+    /// users may do up to a `perf_event_paranoid` level of 2.
+    fn open_event(config: u64, leader: Option<&OwnedFd>) -> io::Result<OwnedFd> {
+        let attr = PerfEventAttr {
+            kind: PERF_TYPE_HARDWARE,
+            size: std::mem::size_of::<PerfEventAttr>() as u32,
+            config,
+            read_format: PERF_FORMAT_GROUP,
+            // the group is enabled and disabled through its leader.
+            flags: if leader.is_none() { ATTR_DISABLED } else { 0 }
+                | ATTR_EXCLUDE_KERNEL
+                | ATTR_EXCLUDE_HV,
+            ..PerfEventAttr::default()
+        };
+
+        let group_fd = leader.map_or(-1, AsRawFd::as_raw_fd);
+
+        // SAFETY: `attr` is a valid perf_event_attr that outlives the call.
+        let fd = unsafe {
+            libc::syscall(
+                libc::SYS_perf_event_open,
+                &attr as *const PerfEventAttr,
+                0,
+                -1,
+                group_fd,
+                PERF_FLAG_FD_CLOEXEC,
+            )
+        };
+
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        // SAFETY: the syscall returned a new fd that nothing else owns.
+        Ok(unsafe { OwnedFd::from_raw_fd(fd as i32) })
+    }
+
+    fn describe_error(err: io::Error) -> String {
+        match err.raw_os_error() {
+            Some(libc::EACCES | libc::EPERM) => {
+                let paranoid = fs::read_to_string("/proc/sys/kernel/perf_event_paranoid")
+                    .map_or("unknown".into(), |level| level.trim().to_string());
+
+                format!(
+                    "hardware counters are not permitted (kernel.perf_event_paranoid = {paranoid}). \
+                    Allow them with `sudo sysctl kernel.perf_event_paranoid=2`."
+                )
+            }
+            Some(libc::ENOENT | libc::ENODEV | libc::EOPNOTSUPP) => {
+                "hardware counters are not supported by this CPU or VM.".into()
+            }
+            _ => format!("could not open hardware counters: {err}"),
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod sys {
+    use super::PerfStats;
+
+    pub enum Group {}
+
+    impl Group {
+        pub fn open() -> Result<Option<Self>, String> {
+            Ok(None)
+        }
+
+        pub fn enable(&self) {}
+
+        pub fn disable(&self) {}
+
+        pub fn read(&self) -> Result<PerfStats, String> {
+            match *self {}
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PerfStats> for JsonValue {
+    fn from(value: &PerfStats) -> Self {
+        let count = |count: Option<u64>| match count {
+            Some(count) => JsonValue::Number(count as f64),
+            None => JsonValue::Null,
+        };
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("instructions".into(), count(Some(value.instructions)));
+        map.insert("cycles".into(), count(Some(value.cycles)));
+        map.insert("branch_misses".into(), count(value.branch_misses));
+        map.insert("cache_misses".into(), count(value.cache_misses));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PerfStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected perf stats to be a JSON object.")?;
+
+        let count = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::Number(count)) => Ok(Some(*count as u64)),
+            Some(_) => Err(format!("Expected perf.{key} to be null or a number.")),
+        };
+        let required =
+            |key: &str| count(key)?.ok_or(format!("Expected perf.{key} to be a number."));
+
+        Ok(PerfStats {
+            instructions: required("instructions")?,
+            cycles: required("cycles")?,
+            branch_misses: count("branch_misses")?,
+            cache_misses: count("cache_misses")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::PerfStats;
+
+    #[test]
+    fn formats_counts() {
+        let stats = PerfStats {
+            instructions: 2_400_000,
+            cycles: 1_200_000,
+            branch_misses: Some(950),
+            cache_misses: None,
+        };
+
+        assert_eq!(stats.ipc(), 2.0);
+        assert_eq!(
+            stats.to_string(),
+            "2.4M instructions · 1.2M cycles · 950 branch misses"
+        );
+    }
+
+    #[test]
+    fn round_trips_stats() {
+        let stats = PerfStats {
+            instructions: 1234,
+            cycles: 5678,
+            branch_misses: None,
+            cache_misses: Some(12),
+        };
+
+        assert_eq!(PerfStats::try_from(&JsonValue::from(&stats)), Ok(stats));
+    }
+}
@@ -0,0 +1,145 @@
+/// Progress bars for long-running solution parts:
+///
+/// ```ignore
+/// let bar = advent_of_code::progress::bar(equations.len());
+///
+/// for equation in equations {
+///     bar.tick();
+///     // ...
+/// }
+/// ```
+///
+/// A bar is drawn on the `Part N:` line of the part it belongs to, and cleared once dropped. It is
+/// only drawn on an interactive terminal, and never on the runs while benching or with `--json`.
+use std::{
+    cell::Cell,
+    env,
+    fmt::Arguments,
+    io::{stderr, stdout, IsTerminal, Write},
+    sync::{Mutex, OnceLock},
+};
+
+use crate::template::report::Step;
+
+// bars are drawn from slices of these, so that drawing does not allocate and does not show up in
+// the allocation counts of a part.
+const FILLED: &str = "##############################";
+const EMPTY: &str = "------------------------------";
+const WIDTH: u64 = FILLED.len() as u64;
+
+/// The step that bars are drawn for, if any.
+static STEP: Mutex<Option<Step>> = Mutex::new(None);
+
+pub struct Bar {
+    total: u64,
+    count: Cell<u64>,
+    /// The count at which the bar is drawn next, so that it is drawn at most once per percent.
+    next_draw: Cell<u64>,
+    step: Option<Step>,
+}
+
+/// Starts a bar that is complete after `total` ticks.
+pub fn bar(total: impl TryInto<u64>) -> Bar {
+    let step = *STEP.lock().unwrap();
+    let bar = Bar {
+        total: total.try_into().unwrap_or(u64::MAX),
+        count: Cell::new(0),
+        // a bar that is not drawn never has to check for it.
+        next_draw: Cell::new(if step.is_some() { 0 } else { u64::MAX }),
+        step,
+    };
+
+    bar.draw();
+    bar
+}
+
+impl Bar {
+    pub fn tick(&self) {
+        self.inc(1);
+    }
+
+    pub fn inc(&self, ticks: u64) {
+        let count = self.count.get() + ticks;
+        self.count.set(count);
+
+        if count >= self.next_draw.get() {
+            self.draw();
+        }
+    }
+
+    fn draw(&self) {
+        let Some(step) = self.step else {
+            return;
+        };
+
+        let count = self.count.get().min(self.total);
+        let total = self.total.max(1);
+        let filled = (count * WIDTH / total) as usize;
+
+        write_out(format_args!(
+            "\r{step}: [{}{}] {}%",
+            &FILLED[..filled],
+            &EMPTY[filled..],
+            count * 100 / total
+        ));
+
+        self.next_draw.set(count + (total / 100).max(1));
+    }
+}
+
+impl Drop for Bar {
+    fn drop(&mut self) {
+        if self.step.is_some() {
+            write_out(format_args!("\r\x1b[2K"));
+        }
+    }
+}
+
+/// Runs `func` with bars drawn on the line of `step`, if the solution runs interactively.
+pub(crate) fn show<T>(step: Step, func: impl FnOnce() -> T) -> T {
+    struct Reset;
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            *STEP.lock().unwrap() = None;
+        }
+    }
+
+    let _reset = Reset;
+
+    if is_interactive() {
+        *STEP.lock().unwrap() = Some(step);
+    }
+
+    func()
+}
+
+/// Solutions run through `cargo solve` are told with `--progress` that their output ends up on a
+/// terminal, as their own output is piped. Read once, as it is checked for every part.
+fn is_interactive() -> bool {
+    static INTERACTIVE: OnceLock<bool> = OnceLock::new();
+
+    *INTERACTIVE.get_or_init(|| {
+        let json = is_json();
+
+        env::args().any(|x| x == "--progress") || (!json && stdout().is_terminal())
+    })
+}
+
+/// Read once, before any bar is drawn, as reading the args allocates.
+fn is_json() -> bool {
+    static JSON: OnceLock<bool> = OnceLock::new();
+
+    *JSON.get_or_init(|| env::args().any(|x| x == "--json"))
+}
+
+/// Writes to where the runner prints its human-readable output, which is stderr with `--json`.
+fn write_out(text: Arguments) {
+    if is_json() {
+        let _ = stderr().lock().write_fmt(text);
+    } else {
+        let mut stdout = stdout().lock();
+        let _ = stdout.write_fmt(text);
+        let _ = stdout.flush();
+    }
+}
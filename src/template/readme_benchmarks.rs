@@ -2,6 +2,7 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::report::Step;
 use crate::template::timings::Timings;
 use crate::template::Day;
 
@@ -59,18 +60,31 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| Day | Parse | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: | :---:  |".into(),
     ];
 
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
+        // stopped steps are not timed, but are told apart from unsolved ones.
+        let cell = |step: Step, time: Option<String>| {
+            time.or_else(|| {
+                timing
+                    .stopped
+                    .iter()
+                    .find(|(stopped, _)| *stopped == step)
+                    .map(|(_, status)| status.to_string())
+            })
+            .unwrap_or_else(|| "-".into())
+        };
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | `{}` | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            cell(Step::Parse, timing.parse),
+            cell(Step::Part(1), timing.part_1),
+            cell(Step::Part(2), timing.part_2)
         ));
     }
 
@@ -100,27 +114,47 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day,
+        template::report::{Status, Step},
+        template::timings::Timing,
+        template::timings::Timings,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
+                    stopped: vec![],
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_2: None,
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
+                    stopped: vec![(Step::Part(2), Status::Panicked)],
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
+                    parse: Some("5ms".into()),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
+                    stopped: vec![],
                     total_nanos: 9e+10,
                 },
             ],
@@ -167,11 +201,11 @@ mod tests {
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `panicked` |",
+            "| [Day 4](./src/bin/04.rs) | `5ms` | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
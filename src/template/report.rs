@@ -0,0 +1,399 @@
+/// Machine-readable results of a solution binary. When invoked with `--json`, a binary prints one
+/// report per line to stdout for its parse step and each part; human-readable output then goes to
+/// stderr instead.
+use std::{collections::HashMap, fmt::Display, str::FromStr, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::{stats::Stats, Day};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part(u8),
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Parse => write!(f, "Parse"),
+            Step::Part(part) => write!(f, "Part {part}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// Emitted before a step runs, so a step that takes the binary down with it can be attributed.
+    Started,
+    Completed,
+    /// The part returned an error instead of an answer.
+    Failed,
+    Panicked,
+    TimedOut,
+    MemoryLimit,
+}
+
+impl Status {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Status::Started => "started",
+            Status::Completed => "completed",
+            Status::Failed => "failed",
+            Status::Panicked => "panicked",
+            Status::TimedOut => "timed_out",
+            Status::MemoryLimit => "memory_limit",
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str().replace('_', " "))
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Status::Started,
+            Status::Completed,
+            Status::Failed,
+            Status::Panicked,
+            Status::TimedOut,
+            Status::MemoryLimit,
+        ]
+        .into_iter()
+        .find(|status| status.as_str() == s)
+        .ok_or(format!("unknown status: {s}"))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub day: Day,
+    /// Name of the input variant the solution ran on, e.g. `alice` for `07.alice.txt`, or the path
+    /// passed with `--input`.
+    pub input: Option<String>,
+    pub step: Step,
+    pub status: Status,
+    pub answer: Option<String>,
+    /// Whether the answer matches the expected answer, if there is one.
+    pub correct: Option<bool>,
+    /// What went wrong, for steps that did not complete.
+    pub error: Option<String>,
+    pub stats: Stats,
+}
+
+impl Report {
+    pub fn started(day: Day, step: Step) -> Self {
+        Self::stopped(day, step, Status::Started, Duration::ZERO)
+    }
+
+    /// Report of a step that did not run to completion.
+    pub fn stopped(day: Day, step: Step, status: Status, after: Duration) -> Self {
+        Report {
+            day,
+            input: None,
+            step,
+            status,
+            answer: None,
+            correct: None,
+            error: None,
+            stats: Stats::single(after),
+        }
+    }
+
+    pub fn to_json_line(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("reports only contain finite numbers")
+    }
+
+    pub fn from_json_line(line: &str) -> Result<Self, String> {
+        let json = JsonValue::from_str(line).or(Err("not valid JSON."))?;
+        Report::try_from(&json)
+    }
+}
+
+/// Aggregates the reports of several days into a single JSON document, for `--json` output of
+/// the CLI.
+pub fn summarize(days: &[(Day, Vec<Report>)]) -> JsonValue {
+    let statuses: HashMap<String, JsonValue> = status_counts(days)
+        .into_iter()
+        .map(|(status, count)| (status.as_str().into(), JsonValue::Number(count as f64)))
+        .collect();
+
+    let mut total_nanos = 0_f64;
+
+    let days = days
+        .iter()
+        .map(|(day, reports)| {
+            total_nanos += reports
+                .iter()
+                .filter(|report| report.status == Status::Completed)
+                .map(|report| report.stats.median.as_nanos() as f64)
+                .sum::<f64>();
+
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+            map.insert("day".into(), JsonValue::String(day.to_string()));
+            map.insert(
+                "reports".into(),
+                JsonValue::Array(reports.iter().map(JsonValue::from).collect()),
+            );
+
+            JsonValue::Object(map)
+        })
+        .collect();
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("days".into(), JsonValue::Array(days));
+    map.insert("statuses".into(), JsonValue::Object(statuses));
+    map.insert("total_nanos".into(), JsonValue::Number(total_nanos));
+
+    JsonValue::Object(map)
+}
+
+/// The number of steps that stopped with each status, e.g. to tell failed parts from panicked ones.
+pub fn status_counts(days: &[(Day, Vec<Report>)]) -> Vec<(Status, usize)> {
+    let mut counts: Vec<(Status, usize)> = vec![];
+
+    for report in days.iter().flat_map(|(_, reports)| reports) {
+        match counts
+            .iter_mut()
+            .find(|(status, _)| *status == report.status)
+        {
+            Some((_, count)) => *count += 1,
+            None => counts.push((report.status, 1)),
+        }
+    }
+
+    counts
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Report> for JsonValue {
+    fn from(value: &Report) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        map.insert(
+            "input".into(),
+            match &value.input {
+                Some(input) => JsonValue::String(input.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "step".into(),
+            match value.step {
+                Step::Parse => JsonValue::String("parse".into()),
+                Step::Part(part) => JsonValue::Number(part.into()),
+            },
+        );
+
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(answer) => JsonValue::String(answer.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "correct".into(),
+            match value.correct {
+                Some(correct) => JsonValue::Boolean(correct),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "error".into(),
+            match &value.error {
+                Some(error) => JsonValue::String(error.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert("stats".into(), JsonValue::from(&value.stats));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Report {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected report.day to be a Day struct.")?;
+
+        let input = match json.get("input") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::String(input)) => Some(input.clone()),
+            _ => return Err("Expected report.input to be null or string.".into()),
+        };
+
+        let step = match json.get("step") {
+            Some(JsonValue::String(step)) if step == "parse" => Step::Parse,
+            Some(JsonValue::Number(part)) => Step::Part(*part as u8),
+            _ => return Err("Expected report.step to be \"parse\" or a part number.".into()),
+        };
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.status to be a string.")?
+            .parse()?;
+
+        let answer = match json.get("answer") {
+            Some(JsonValue::Null) => None,
+            Some(JsonValue::String(answer)) => Some(answer.clone()),
+            _ => return Err("Expected report.answer to be null or string.".into()),
+        };
+
+        let correct = match json.get("correct") {
+            Some(JsonValue::Null) => None,
+            Some(JsonValue::Boolean(correct)) => Some(*correct),
+            _ => return Err("Expected report.correct to be null or boolean.".into()),
+        };
+
+        let error = match json.get("error") {
+            Some(JsonValue::Null) => None,
+            Some(JsonValue::String(error)) => Some(error.clone()),
+            _ => return Err("Expected report.error to be null or string.".into()),
+        };
+
+        let stats = json
+            .get("stats")
+            .ok_or("Expected report to have key `stats`.")
+            .map(Stats::try_from)??;
+
+        Ok(Report {
+            day,
+            input,
+            step,
+            status,
+            answer,
+            correct,
+            error,
+            stats,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{status_counts, Report, Status, Step};
+    use crate::day;
+    use crate::template::stats::Stats;
+
+    #[test]
+    fn round_trips_reports() {
+        let report = Report {
+            day: day!(8),
+            input: Some("alice".into()),
+            step: Step::Part(2),
+            status: Status::Completed,
+            // answers may contain anything, including what used to be scraped from the output.
+            answer: Some("@ (1.2ms @ 10 samples)\n\"quoted\"".into()),
+            correct: Some(false),
+            error: None,
+            stats: Stats::from_samples(
+                Duration::from_micros(3),
+                &[Duration::from_nanos(1200), Duration::from_nanos(1300)],
+            ),
+        };
+
+        let line = report.to_json_line();
+        assert!(!line.contains('\n'));
+        assert_eq!(Report::from_json_line(&line), Ok(report));
+    }
+
+    #[test]
+    fn round_trips_unsolved_parse_step() {
+        let report = Report {
+            day: day!(1),
+            input: None,
+            step: Step::Parse,
+            status: Status::Completed,
+            answer: None,
+            correct: None,
+            error: None,
+            stats: Stats::single(Duration::from_micros(5)),
+        };
+
+        assert_eq!(Report::from_json_line(&report.to_json_line()), Ok(report));
+    }
+
+    #[test]
+    fn round_trips_stopped_steps() {
+        let report = Report::stopped(
+            day!(6),
+            Step::Part(2),
+            Status::TimedOut,
+            Duration::from_secs(10),
+        );
+
+        assert_eq!(Report::from_json_line(&report.to_json_line()), Ok(report));
+
+        let report = Report {
+            error: Some("panicked at src/bin/06.rs:12:5: explicit panic".into()),
+            ..Report::stopped(day!(6), Step::Part(1), Status::Panicked, Duration::ZERO)
+        };
+
+        assert_eq!(Report::from_json_line(&report.to_json_line()), Ok(report));
+    }
+
+    #[test]
+    fn rejects_other_lines() {
+        assert!(Report::from_json_line("Part 1: 42 (1.0ms)").is_err());
+        assert!(Report::from_json_line("{\"day\": \"01\"}").is_err());
+    }
+
+    #[test]
+    fn counts_statuses() {
+        let stopped = |status| Report::stopped(day!(7), Step::Part(1), status, Duration::ZERO);
+        let days = [
+            (
+                day!(6),
+                vec![stopped(Status::Completed), stopped(Status::Failed)],
+            ),
+            (
+                day!(7),
+                vec![stopped(Status::Completed), stopped(Status::Panicked)],
+            ),
+        ];
+
+        assert_eq!(
+            status_counts(&days),
+            vec![
+                (Status::Completed, 2),
+                (Status::Failed, 1),
+                (Status::Panicked, 1)
+            ]
+        );
+    }
+}
@@ -1,14 +1,29 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::HashSet,
+    io::{self, IsTerminal},
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    bench_options::BenchOptions, limits::Limits, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
+    report::{self, Report, Status},
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    alloc_stats: bool,
+    bench_options: &BenchOptions,
+    limits: &Limits,
+    is_json: bool,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut reports: Vec<(Day, Vec<Report>)> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
 
@@ -17,28 +32,65 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         .filter(|day| days_to_run.contains(day))
         .for_each(|day| {
             if need_space {
-                println!();
+                say(is_json, "");
             }
             need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+            say(is_json, &format!("{ANSI_BOLD}Day {day}{ANSI_RESET}"));
+            say(is_json, "------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let limits = limits.with_day(day).unwrap_or_else(|err| {
+                eprintln!("Invalid limits for day {day}: {err}");
+                std::process::exit(1);
+            });
 
-            if output.is_empty() {
-                println!("Not solved.");
+            let day_reports = child_commands::run_solution(
+                day,
+                is_timed,
+                is_release,
+                alloc_stats,
+                bench_options,
+                &limits,
+                is_json,
+            )
+            .unwrap();
+
+            if day_reports.is_empty() {
+                say(is_json, "Not solved.");
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+                timings.push(child_commands::timing_from_reports(day, &day_reports));
+                reports.push((day, day_reports));
             }
         });
 
+    let stopped: Vec<String> = report::status_counts(&reports)
+        .into_iter()
+        .filter(|(status, _)| *status != Status::Completed)
+        .map(|(status, count)| format!("{count} {status}"))
+        .collect();
+
+    if !stopped.is_empty() {
+        say(
+            is_json,
+            &format!(
+                "\n{ANSI_BOLD}Not completed:{ANSI_RESET} {}",
+                stopped.join(", ")
+            ),
+        );
+    }
+
+    if is_json {
+        println!("{}", report::summarize(&reports).stringify().unwrap());
+    }
+
     if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+        say(
+            is_json,
+            &format!(
+                "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            ),
         );
         Some(timings)
     } else {
@@ -46,6 +98,20 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+/// Whether human-readable output ends up on a terminal, where progress bars can be drawn.
+pub fn is_interactive(is_json: bool) -> bool {
+    !is_json && io::stdout().is_terminal()
+}
+
+/// Prints human-readable output, which goes to stderr when stdout is reserved for `--json` output.
+pub fn say(is_json: bool, line: &str) {
+    if is_json {
+        eprintln!("{line}");
+    } else {
+        println!("{line}");
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -65,19 +131,34 @@ pub fn get_path_for_bin(day: Day) -> String {
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the reports they emit.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{get_path_for_bin, is_interactive, say, Error};
+    use crate::template::{
+        bench_options::BenchOptions,
+        limits::Limits,
+        report::{Report, Status, Step},
+        timings::Timing,
+        Day,
+    };
     use std::{
-        io::{BufRead, BufReader},
+        io::{self, BufRead, BufReader, Write},
         path::Path,
         process::{Command, Stdio},
         thread,
+        time::Instant,
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        alloc_stats: bool,
+        bench_options: &BenchOptions,
+        limits: &Limits,
+        is_json: bool,
+    ) -> Result<Vec<Report>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
@@ -90,103 +171,150 @@ pub mod child_commands {
             args.push("--release");
         }
 
+        if alloc_stats {
+            args.extend(["--features", "alloc-stats"]);
+        }
+
+        // children always report their results as JSON lines.
+        args.extend(["--", "--json"]);
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        if is_interactive(is_json) {
+            args.push("--progress");
+        }
+
+        let child_args = [bench_options.to_args(), limits.to_args()].concat();
+        args.extend(child_args.iter().map(String::as_str));
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        let mut cmd = Command::new("cargo");
+        cmd.args(&args);
+
+        collect_reports(cmd, is_json)
+    }
+
+    /// Spawn a solution bin invoked with `--json`, collecting the reports it prints to stdout.
+    /// Any other output is forwarded, to stderr if `is_json` is set and to stdout otherwise.
+    pub fn collect_reports(mut cmd: Command, is_json: bool) -> Result<Vec<Report>, Error> {
+        let mut cmd = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut reports = vec![];
+        // the step that was started but has not been reported on yet, if any.
+        let mut running = None;
 
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+            let mut stderr = stderr;
+            let mut line = vec![];
+            let mut out_of_memory = false;
+
+            // forwarded as it arrives rather than by line, so that progress bars are drawn.
+            loop {
+                let chunk = stderr.fill_buf().unwrap();
+                if chunk.is_empty() {
+                    break;
+                }
+
+                forward(is_json, chunk);
+
+                for &byte in chunk {
+                    if byte == b'\n' {
+                        // printed by the default allocation error handler before aborting.
+                        out_of_memory |= line.starts_with(b"memory allocation of");
+                        line.clear();
+                    } else {
+                        line.push(byte);
+                    }
+                }
+
+                let len = chunk.len();
+                stderr.consume(len);
+            }
+
+            out_of_memory | line.starts_with(b"memory allocation of")
         });
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+
+            match Report::from_json_line(&line) {
+                Ok(report) if report.status == Status::Started => {
+                    running = Some((report, Instant::now()));
+                }
+                Ok(report) => {
+                    running = None;
+                    reports.push(report);
+                }
+                Err(_) => say(is_json, &line),
+            }
         }
 
-        thread.join().unwrap();
+        let out_of_memory = thread.join().unwrap();
         cmd.wait()?;
 
-        Ok(output)
+        if let Some((started, at)) = running.filter(|_| out_of_memory) {
+            say(is_json, &format!("{}: memory limit", started.step));
+            reports.push(Report {
+                input: started.input,
+                ..Report::stopped(started.day, started.step, Status::MemoryLimit, at.elapsed())
+            });
+        }
+
+        Ok(reports)
+    }
+
+    /// Writes output of a child as is, to stderr if `is_json` is set and to stdout otherwise.
+    fn forward(is_json: bool, bytes: &[u8]) {
+        let _ = if is_json {
+            io::stderr().write_all(bytes)
+        } else {
+            let mut stdout = io::stdout();
+            stdout.write_all(bytes).and_then(|()| stdout.flush())
+        };
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
+    pub fn timing_from_reports(day: Day, reports: &[Report]) -> Timing {
+        let mut timing = Timing {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
+            parse_alloc: None,
+            part_1_alloc: None,
+            part_2_alloc: None,
+            stopped: vec![],
             total_nanos: 0_f64,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+        for report in reports {
+            let (slot, alloc_slot) = match report.status {
+                Status::Started => continue,
+                // stopped steps are not timed, but keep how they stopped.
+                Status::Failed | Status::Panicked | Status::TimedOut | Status::MemoryLimit => {
+                    timing.stopped.push((report.step, report.status));
+                    continue;
                 }
+                Status::Completed => match report.step {
+                    // unsolved parts are not timed.
+                    Step::Part(_) if report.answer.is_none() => continue,
+                    Step::Parse => (&mut timing.parse, &mut timing.parse_alloc),
+                    Step::Part(1) => (&mut timing.part_1, &mut timing.part_1_alloc),
+                    Step::Part(2) => (&mut timing.part_2, &mut timing.part_2_alloc),
+                    Step::Part(_) => continue,
+                },
+            };
+
+            *slot = Some(format!("{:.1?}", report.stats.median));
+            *alloc_slot = report.stats.alloc;
+            timing.total_nanos += report.stats.median.as_nanos() as f64;
+        }
 
-                timings.total_nanos += nanos;
-            });
-
-        timings
-    }
-
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        timing
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,53 +333,136 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use std::time::Duration;
+
+        use super::timing_from_reports;
 
         use crate::day;
+        use crate::template::{
+            report::{Report, Status, Step},
+            stats::Stats,
+        };
+
+        fn report(step: Step, answer: Option<&str>, nanos: u64) -> Report {
+            Report {
+                day: day!(1),
+                input: None,
+                step,
+                status: Status::Completed,
+                answer: answer.map(Into::into),
+                correct: None,
+                error: None,
+                stats: Stats::single(Duration::from_nanos(nanos)),
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_execution_times() {
+            let res = timing_from_reports(
+                day!(1),
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(Step::Part(1), Some("0"), 74),
+                    report(Step::Part(2), Some("10"), 74_130_000),
                 ],
-                day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_with_patterns_in_answers() {
+            let res = timing_from_reports(
+                day!(1),
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    report(
+                        Step::Part(1),
+                        Some("@ @ @ ( ) ms (2s @ 5 samples)"),
+                        2_000_000_000,
+                    ),
+                    report(Step::Part(2), Some("10s"), 100_000_000),
                 ],
-                day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn collects_parse_times() {
+            let res = timing_from_reports(
+                day!(1),
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    report(Step::Parse, None, 5_000),
+                    report(Step::Part(1), Some("0"), 74),
+                    report(Step::Part(2), Some("10"), 74),
                 ],
+            );
+            assert_approx_eq!(res.total_nanos, 5148_f64);
+            assert_eq!(res.parse.unwrap(), "5.0µs");
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+        }
+
+        #[test]
+        fn skips_missing_parts() {
+            let res = timing_from_reports(
                 day!(1),
+                &[
+                    report(Step::Part(1), None, 74),
+                    report(Step::Part(2), None, 74),
+                ],
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn skips_stopped_parts() {
+            let res = timing_from_reports(
+                day!(6),
+                &[
+                    report(Step::Part(1), Some("41"), 1_000),
+                    Report::stopped(
+                        day!(6),
+                        Step::Part(2),
+                        Status::TimedOut,
+                        Duration::from_secs(10),
+                    ),
+                ],
+            );
+            assert_approx_eq!(res.total_nanos, 1000_f64);
+            assert_eq!(res.part_1.unwrap(), "1.0µs");
+            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.stopped, [(Step::Part(2), Status::TimedOut)]);
+        }
+
+        #[test]
+        fn keeps_how_steps_stopped() {
+            let res = timing_from_reports(
+                day!(6),
+                &[
+                    Report::started(day!(6), Step::Parse),
+                    report(Step::Parse, None, 1_000),
+                    Report {
+                        error: Some("panicked at src/bin/06.rs:12:5: explicit panic".into()),
+                        ..Report::stopped(day!(6), Step::Part(1), Status::Panicked, Duration::ZERO)
+                    },
+                    Report {
+                        error: Some("no guard found".into()),
+                        ..Report::stopped(day!(6), Step::Part(2), Status::Failed, Duration::ZERO)
+                    },
+                ],
+            );
+            assert_eq!(res.parse.unwrap(), "1.0µs");
+            assert_eq!(res.part_1.is_none() && res.part_2.is_none(), true);
+            assert_eq!(
+                res.stopped,
+                [
+                    (Step::Part(1), Status::Panicked),
+                    (Step::Part(2), Status::Failed)
+                ]
+            );
+        }
     }
 }
@@ -1,155 +1,557 @@
 /// Encapsulates code that interacts with solution functions.
+use std::cell::Cell;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{stdin, stdout, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Mutex, Once, OnceLock};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process, thread};
 
+use crate::ocr;
+use crate::template::bench_options::BenchOptions;
+use crate::template::error::PartResult;
+use crate::template::limits::Limits;
+use crate::template::perf_counters::Counters;
+use crate::template::report::{Report, Status, Step};
+use crate::template::stats::Stats;
+use crate::template::submissions::{self, Submissions, Verdict};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    alloc_stats, answers, aoc_cli, progress, verbosity, viz, Day, ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn run_part<I: Clone, T: Display + PartialEq>(
-    func: impl Fn(I) -> Option<T>,
+/// Prints human-readable output. With `--json`, stdout is reserved for reports and this prints to
+/// stderr instead.
+macro_rules! out {
+    ($($arg:tt)*) => {
+        if is_json() {
+            eprint!($($arg)*);
+        } else {
+            print!($($arg)*);
+        }
+    };
+}
+
+macro_rules! outln {
+    ($($arg:tt)*) => {
+        if is_json() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Run a solution part, which returns an `Option` of its answer or a `Result` of it.
+pub fn run_part<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
     input: I,
     day: Day,
     part: u8,
-    expect: Option<T>,
+    expect: Option<R::Answer>,
 ) {
-    let part_str = format!("Part {part}");
+    run_part_with_state(|input| (func(input), ()), input, day, part, expect);
+}
+
+/// Run a solution part that also produces some state, e.g. part one of a solution declared with
+/// `chain`. The answer and the state are returned so they can be handed on to part two, unless the
+/// part panicked.
+pub fn run_part_with_state<I: Clone, R: PartResult, S>(
+    func: impl Fn(I) -> (R, S),
+    input: I,
+    day: Day,
+    part: u8,
+    expect: Option<R::Answer>,
+) -> Option<(Option<R::Answer>, S)> {
+    let part_str = Step::Part(part).to_string();
+    // answers are not known for an input passed with `--input`.
+    let expect = match input_path() {
+        Some(_) => None,
+        None => {
+            answers::expected(day, input_variant().as_deref(), part, expect).unwrap_or_else(|err| {
+                eprintln!("Invalid answers file: {err}");
+                process::exit(1);
+            })
+        }
+    };
+
+    let ((result, state), stats) = run_step(day, Step::Part(part), func, input, |(result, _)| {
+        print_result(&result.answer(), &part_str, "", &expect)
+    })?;
+
+    let answer = result.answer();
+    print_result(&answer, &part_str, &format_duration(&stats), &expect);
+    print_stats(&stats);
+
+    let (status, error) = match &answer {
+        Ok(_) => (Status::Completed, None),
+        Err(err) => (Status::Failed, Some(err.clone())),
+    };
+    let answer_str = answer
+        .as_ref()
+        .ok()
+        .and_then(|answer| answer.map(ToString::to_string));
 
-    let (result, duration, samples) = run_timed(func, input, |result| {
-        print_result(result, &part_str, "", &expect)
+    emit_report(Report {
+        day,
+        input: None,
+        step: Step::Part(part),
+        status,
+        correct: expect.as_ref().map(|expect| {
+            answer_str
+                .as_ref()
+                .is_some_and(|answer| answers::matches(answer, expect))
+        }),
+        answer: answer_str,
+        error,
+        stats,
     });
 
-    print_result(
-        &result,
-        &part_str,
-        &format_duration(&duration, samples),
-        &expect,
-    );
+    submit_result(&answer, day, part);
+
+    Some((result.into_answer(), state))
+}
 
-    if let Some(result) = &result {
-        submit_result(result, day, part);
+/// Run the parser of a solution declared with `parse = ...`, timing it like a part. Returns `None`
+/// if the parser panicked.
+pub fn run_parse<'a, P>(func: impl Fn(&'a str) -> P, input: &'a str, day: Day) -> Option<P> {
+    let (parsed, stats) = run_step(day, Step::Parse, func, input, |_| out!("Parse:"))?;
+
+    out!("\r");
+    outln!("Parse:{}", format_duration(&stats));
+    print_stats(&stats);
+
+    emit_report(Report {
+        day,
+        input: None,
+        step: Step::Parse,
+        status: Status::Completed,
+        answer: None,
+        correct: None,
+        error: None,
+        stats,
+    });
+
+    Some(parsed)
+}
+
+/// Run a step of a solution under the configured limits, reporting when it starts. A panic in the
+/// step is caught and reported, and `None` is returned so the other steps can still run.
+fn run_step<I: Clone, T>(
+    day: Day,
+    step: Step,
+    func: impl Fn(I) -> T,
+    input: I,
+    hook: impl Fn(&T),
+) -> Option<(T, Stats)> {
+    emit_report(Report::started(day, step));
+
+    let timer = Instant::now();
+
+    match catch_panic(|| run_timed(day, step, func, input, hook)) {
+        Ok(timed) => Some(timed),
+        Err(error) => {
+            out!("\r");
+            outln!("{step}: ✖ {ANSI_ITALIC}{error}{ANSI_RESET}");
+
+            emit_report(Report {
+                error: Some(error),
+                ..Report::stopped(day, step, Status::Panicked, timer.elapsed())
+            });
+
+            None
+        }
     }
 }
 
+thread_local! {
+    static CATCHING_PANICS: Cell<bool> = const { Cell::new(false) };
+}
+
+static CAUGHT_PANIC: Mutex<Option<String>> = Mutex::new(None);
+
+/// Runs `func`, catching a panic and returning its message and location instead. The default
+/// panic output is suppressed for caught panics, but kept for panics anywhere else.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    static HOOK: Once = Once::new();
+
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING_PANICS.get() {
+                return default_hook(info);
+            }
+
+            let payload = info.payload();
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or(payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("Box<dyn Any>");
+
+            let error = match info.location() {
+                Some(location) => format!("panicked at {location}: {message}"),
+                None => format!("panicked: {message}"),
+            };
+
+            *CAUGHT_PANIC.lock().unwrap() = Some(error);
+        }));
+    });
+
+    CATCHING_PANICS.set(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    CATCHING_PANICS.set(false);
+
+    result.map_err(|_| {
+        CAUGHT_PANIC
+            .lock()
+            .unwrap()
+            .take()
+            .unwrap_or_else(|| "panicked".into())
+    })
+}
+
+/// Exits the process once a step runs past the timeout, reporting the step as timed out. The step
+/// counts as done once the watchdog is dropped.
+struct Watchdog {
+    _done: Option<mpsc::Sender<()>>,
+}
+
+impl Watchdog {
+    fn start(day: Day, step: Step) -> Self {
+        let Some(timeout) = limits().timeout else {
+            return Self { _done: None };
+        };
+
+        let (done, wait) = mpsc::channel();
+
+        thread::spawn(move || {
+            if wait.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout) {
+                out!("\r");
+                outln!("{step}: ⏱ timed out ({timeout:.1?})");
+                emit_report(Report::stopped(day, step, Status::TimedOut, timeout));
+                let _ = stdout().flush();
+
+                process::exit(TIMED_OUT_EXIT_CODE);
+            }
+        });
+
+        Self { _done: Some(done) }
+    }
+}
+
+/// Same as the exit code of the `timeout` utility.
+const TIMED_OUT_EXIT_CODE: i32 = 124;
+
+/// The limits a solution binary runs under. The memory limit is applied on first use.
+fn limits() -> &'static Limits {
+    static LIMITS: OnceLock<Limits> = OnceLock::new();
+
+    LIMITS.get_or_init(|| {
+        let limits = Limits::from_env().and_then(|limits| {
+            limits.apply_memory_limit()?;
+            Ok(limits)
+        });
+
+        limits.unwrap_or_else(|err| {
+            eprintln!("Invalid limits: {err}");
+            process::exit(1);
+        })
+    })
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer,
+///     unless configured otherwise through `BenchOptions`.)
 fn run_timed<I: Clone, T>(
+    day: Day,
+    step: Step,
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
-        let input = input.clone();
+) -> (T, Stats) {
+    // progress and visualizations are only shown on the first run, never the runs while benching.
+    let (result, alloc, base_time) = viz::show(|| {
+        // only the first run is limited, benching stops by itself once past the timeout.
+        let _watchdog = Watchdog::start(day, step);
 
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+        // bars are set up before the timer starts, and draw without allocating.
+        progress::show(step, || {
+            let timer = Instant::now();
+            let input = input.clone();
 
-        func(input)
-    };
-    let base_time = timer.elapsed();
+            #[cfg(feature = "dhat-heap")]
+            let _profiler = dhat::Profiler::new_heap();
+
+            let (result, alloc) = alloc_stats::measure(|| func(input));
+            (result, alloc, timer.elapsed())
+        })
+    });
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let mut stats = if std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        Stats::single(base_time)
     };
 
-    (result, run.0, run.1)
-}
+    // only set when the counting allocator of the `alloc-stats` feature is installed.
+    stats.alloc = alloc_stats::is_counting().then_some(alloc);
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+    (result, stats)
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Stats {
+    out!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+    let _ = stdout().flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let (bench_time, (min_samples, max_samples)) = bench_limits();
+    let bench_iterations = (bench_time.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(min_samples, max_samples);
 
     let mut timers: Vec<Duration> = vec![];
+    let mut counters = open_counters();
+
+    // stops sampling early rather than running past the timeout, expecting a run to take as long
+    // as the first one.
+    let deadline = limits().timeout.map(|timeout| Instant::now() + timeout);
+    let out_of_time = || deadline.is_some_and(|deadline| Instant::now() + *base_time > deadline);
+
+    // debug output of the solution is only printed on the first run.
+    verbosity::silenced(|| {
+        // warm caches and branch predictors before taking any samples.
+        for _ in 0..cmp::max(bench_iterations / 10, 1) {
+            if out_of_time() {
+                return;
+            }
+
+            black_box(func(black_box(input.clone())));
+        }
+
+        for _ in 0..bench_iterations {
+            if out_of_time() {
+                return;
+            }
+
+            // need a clone here to make the borrow checker happy.
+            let cloned = input.clone();
+            let sample = || {
+                let timer = Instant::now();
+                black_box(func(black_box(cloned)));
+                timer.elapsed()
+            };
 
-    for _ in 0..bench_iterations {
-        // need a clone here to make the borrow checker happy.
-        let cloned = input.clone();
-        let timer = Instant::now();
-        black_box(func(black_box(cloned)));
-        timers.push(timer.elapsed());
+            timers.push(match &mut counters {
+                Some(counters) => counters.measure(sample),
+                None => sample(),
+            });
+        }
+    });
+
+    let mut stats = Stats::from_samples(*base_time, &timers);
+    stats.perf = counters.and_then(|counters| {
+        counters
+            .read()
+            .inspect_err(|err| outln!("\n{ANSI_ITALIC}{err}{ANSI_RESET}"))
+            .ok()
+    });
+
+    stats
+}
+
+/// Opens hardware counters for benching. If they are unavailable, says why once per binary.
+fn open_counters() -> Option<Counters> {
+    static WARNED: Once = Once::new();
+
+    Counters::open().unwrap_or_else(|err| {
+        WARNED.call_once(|| outln!("\n{ANSI_ITALIC}{err}{ANSI_RESET}"));
+        None
+    })
+}
+
+/// Whether the binary was invoked with `--json` and should report its results as JSON lines.
+fn is_json() -> bool {
+    env::args().any(|x| x == "--json")
+}
+
+fn emit_report(report: Report) {
+    if is_json() {
+        let report = Report {
+            input: input_path().or_else(input_variant),
+            ..report
+        };
+
+        println!("{}", report.to_json_line());
     }
+}
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+/// Reads the input of a solution, or the variant of it passed with `--variant <name>`. An input
+/// passed with `--input <path>` takes precedence, where a path of `-` reads from stdin.
+pub fn read_input(day: Day) -> String {
+    let path = match (input_path(), input_variant()) {
+        (Some(path), _) if path == "-" => {
+            let mut input = String::new();
+            if let Err(err) = stdin().read_to_string(&mut input) {
+                eprintln!("Could not read input from stdin: {err}");
+                process::exit(1);
+            }
+            return input;
+        }
+        (Some(path), _) => PathBuf::from(path),
+        (None, Some(variant)) => Path::new("data")
+            .join("inputs")
+            .join(format!("{day}.{variant}.txt")),
+        (None, None) => Path::new("data").join("inputs").join(format!("{day}.txt")),
+    };
+
+    fs::read_to_string(&path).unwrap_or_else(|err| {
+        eprintln!("Could not read input file \"{}\": {err}", path.display());
+        if input_path().is_none() && input_variant().is_none() {
+            eprintln!("Download it with \"cargo download {day}\".");
+        }
+        process::exit(1);
+    })
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// The input file the binary was invoked with, if any.
+fn input_path() -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|arg| arg == "--input")?;
+
+    match args.get(index + 1) {
+        Some(path) => Some(path.clone()),
+        None => {
+            eprintln!("Unexpected command-line input. Format: --input <path>");
+            process::exit(1);
+        }
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+/// The name of the input variant the binary was invoked with, if any.
+fn input_variant() -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|arg| arg == "--variant")?;
+
+    match args.get(index + 1) {
+        Some(variant) => Some(variant.clone()),
+        None => {
+            eprintln!("Unexpected command-line input. Format: --variant <name>");
+            process::exit(1);
+        }
+    }
+}
+
+fn bench_limits() -> (Duration, (u128, u128)) {
+    let limits =
+        BenchOptions::from_env().and_then(|options| Ok((options.time(), options.samples()?)));
+
+    limits.unwrap_or_else(|err| {
+        eprintln!("\nInvalid bench options: {err}");
+        process::exit(1);
+    })
+}
+
+fn format_duration(stats: &Stats) -> String {
+    let alloc = match &stats.alloc {
+        Some(alloc) => format!(" · {alloc}"),
+        None => String::new(),
+    };
+
+    let ipc = match &stats.perf {
+        Some(perf) => format!(" · {:.2} IPC", perf.ipc()),
+        None => String::new(),
+    };
+
+    if stats.samples == 1 {
+        format!(" ({:.1?}{alloc})", stats.median)
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({:.1?} ± {:.1?} @ {} samples{ipc}{alloc})",
+            stats.median, stats.mad, stats.samples
+        )
     }
 }
 
-fn print_result<T: Display + PartialEq>(
-    result: &Option<T>,
+fn print_stats(stats: &Stats) {
+    if stats.samples == 1 {
+        return;
+    }
+
+    outln!(
+        "        {ANSI_ITALIC}cold {:.1?} · min {:.1?} · mean {:.1?} · p95 {:.1?} · max {:.1?} · σ {:.1?} · {} outliers{ANSI_RESET}",
+        stats.cold, stats.min, stats.mean, stats.p95, stats.max, stats.std_dev, stats.outliers
+    );
+
+    if let Some(perf) = &stats.perf {
+        outln!("        {ANSI_ITALIC}per run {perf}{ANSI_RESET}");
+    }
+}
+
+fn print_result<T: Display>(
+    result: &Result<Option<&T>, String>,
     part: &str,
     duration_str: &str,
-    expect: &Option<T>,
+    expect: &Option<String>,
 ) {
     let is_intermediate_result = duration_str.is_empty();
-    let match_str = if expect.is_some() {
-        if result == expect {
+    let match_str = match (result, expect) {
+        (_, None) => "".to_string(),
+        (Ok(Some(result)), Some(expect)) if answers::matches(&result.to_string(), expect) => {
             "✅".to_string()
-        } else {
-            format!("❌ (expected {})", expect.as_ref().unwrap())
         }
-    } else {
-        "".to_string()
+        (_, Some(expect)) => format!("❌ (expected {expect})"),
     };
 
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+        Ok(Some(result)) => {
+            let result_str = result.to_string();
+            if result_str.contains('\n') {
+                // answers drawn in block letters are shown together with the letters read.
+                let str = match ocr::parse(&result_str) {
+                    Some(letters) => {
+                        format!("{part}: ▼ {ANSI_BOLD}{letters}{ANSI_RESET}{duration_str}")
+                    }
+                    None => format!("{part}: ▼ {duration_str}"),
+                };
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result} {match_str}");
+                    out!("\r");
+                    outln!("{str}");
+                    outln!("{result} {match_str}");
                 }
             } else {
                 let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str} {match_str}");
+                    out!("\r");
+                    outln!("{str} {match_str}");
                 }
             }
         }
-        None => {
+        Err(err) => {
+            if is_intermediate_result {
+                out!("{part}: ✖");
+            } else {
+                out!("\r");
+                outln!(
+                    "{part}: ✖ {ANSI_ITALIC}failed: {err}{ANSI_RESET}{duration_str} {match_str}"
+                );
+            }
+        }
+        Ok(None) => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                out!("{part}: ✖");
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                out!("\r");
+                outln!("{part}: ✖             ");
             }
         }
     }
@@ -158,15 +560,12 @@ fn print_result<T: Display + PartialEq>(
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  3. the submission log does not rule out the answer, see `Submissions::check`.
+fn submit_result<T: Display>(result: &Result<Option<&T>, String>, day: Day, part: u8) {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -182,7 +581,29 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
+    }
+
+    let result = match result {
+        Ok(Some(result)) => result,
+        Ok(None) => {
+            outln!("Not submitting: part {part} has no answer.");
+            return;
+        }
+        Err(_) => {
+            outln!("Not submitting: part {part} failed.");
+            return;
+        }
+    };
+
+    if let Some(path) = input_path() {
+        outln!("Not submitting: the answer is for input {path}.");
+        return;
+    }
+
+    if let Some(variant) = input_variant() {
+        outln!("Not submitting: the answer is for input variant {variant}.");
+        return;
     }
 
     if aoc_cli::check().is_err() {
@@ -190,6 +611,61 @@ fn submit_result<T: Display>(
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.to_string();
+    // answers drawn in block letters are submitted as the letters read.
+    let answer = if answer.contains('\n') {
+        let Some(letters) = ocr::parse(&answer) else {
+            outln!("Not submitting: part {part} spans several lines that could not be read as letters.");
+            return;
+        };
+        letters
+    } else {
+        answer
+    };
+    let mut submissions = Submissions::read_from_file().unwrap_or_else(|err| {
+        eprintln!("Invalid submissions file: {err}");
+        process::exit(1);
+    });
+
+    if let Err(reason) = submissions.check(day, part, &answer, submissions::now()) {
+        outln!("Not submitting {answer}: {reason}");
+        return;
+    }
+
+    outln!("Submitting result via aoc-cli...");
+
+    let (submission, response) = match submissions.submit(day, part, &answer, submissions::now()) {
+        Ok(submitted) => submitted,
+        Err(err) => {
+            eprintln!("Failed to submit: {err}");
+            return;
+        }
+    };
+
+    out!("{response}");
+
+    if let Err(err) = submissions.store_file() {
+        eprintln!("Failed to store submission: {err}");
+    }
+
+    if submission.verdict == Verdict::Correct {
+        match answers::record(day, part, &answer) {
+            Ok(()) => outln!("Stored answer in data/answers.json."),
+            Err(err) => eprintln!("Failed to store answer: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::catch_panic;
+
+    #[test]
+    fn catches_panics_with_location() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+
+        let error = catch_panic(|| -> u32 { panic!("no {} found", "guard") }).unwrap_err();
+        assert!(error.starts_with("panicked at src/template/runner.rs:"));
+        assert!(error.ends_with(": no guard found"));
+    }
 }
@@ -0,0 +1,239 @@
+/// Summary statistics over the samples collected when benching a solution part.
+use std::{collections::HashMap, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::{alloc_stats::AllocStats, perf_counters::PerfStats};
+
+/// Samples further than this many (scaled) median absolute deviations from the median are
+/// considered outliers and excluded from the summary.
+const OUTLIER_THRESHOLD: f64 = 5.0;
+
+/// Scales the median absolute deviation to be comparable with the standard deviation of normally
+/// distributed samples.
+const MAD_SCALE: f64 = 1.4826;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    /// Duration of the first, un-warmed run.
+    pub cold: Duration,
+    pub samples: u128,
+    pub outliers: u128,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+    /// Scaled median absolute deviation, used as the spread around the median.
+    pub mad: Duration,
+    /// Allocations of the cold run, if they were counted.
+    pub alloc: Option<AllocStats>,
+    /// Hardware counters per benched run, where available.
+    pub perf: Option<PerfStats>,
+}
+
+impl Stats {
+    /// Stats for a part that was only run once.
+    pub fn single(duration: Duration) -> Self {
+        Self {
+            cold: duration,
+            samples: 1,
+            outliers: 0,
+            min: duration,
+            median: duration,
+            mean: duration,
+            p95: duration,
+            max: duration,
+            std_dev: Duration::ZERO,
+            mad: Duration::ZERO,
+            alloc: None,
+            perf: None,
+        }
+    }
+
+    pub fn from_samples(cold: Duration, samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::single(cold);
+        }
+
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let median = percentile(&nanos, 50.0);
+        let mad = median_absolute_deviation(&nanos, median) * MAD_SCALE;
+
+        let kept: Vec<f64> = if mad > 0.0 {
+            nanos
+                .iter()
+                .copied()
+                .filter(|x| (x - median).abs() <= OUTLIER_THRESHOLD * mad)
+                .collect()
+        } else {
+            nanos.clone()
+        };
+
+        let mean = kept.iter().sum::<f64>() / kept.len() as f64;
+        let variance = kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / kept.len() as f64;
+
+        Self {
+            cold,
+            samples: samples.len() as u128,
+            outliers: (nanos.len() - kept.len()) as u128,
+            min: from_nanos(kept[0]),
+            median: from_nanos(percentile(&kept, 50.0)),
+            mean: from_nanos(mean),
+            p95: from_nanos(percentile(&kept, 95.0)),
+            max: from_nanos(kept[kept.len() - 1]),
+            std_dev: from_nanos(variance.sqrt()),
+            mad: from_nanos(mad),
+            alloc: None,
+            perf: None,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let nanos = |d: Duration| JsonValue::Number(d.as_nanos() as f64);
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("cold".into(), nanos(value.cold));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+        map.insert("min".into(), nanos(value.min));
+        map.insert("median".into(), nanos(value.median));
+        map.insert("mean".into(), nanos(value.mean));
+        map.insert("p95".into(), nanos(value.p95));
+        map.insert("max".into(), nanos(value.max));
+        map.insert("std_dev".into(), nanos(value.std_dev));
+        map.insert("mad".into(), nanos(value.mad));
+        map.insert(
+            "alloc".into(),
+            match &value.alloc {
+                Some(alloc) => JsonValue::from(alloc),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "perf".into(),
+            match &value.perf {
+                Some(perf) => JsonValue::from(perf),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+        let duration = |key: &str| number(key).map(from_nanos);
+
+        // allocations are only counted with the `alloc-stats` feature.
+        let alloc = match json.get("alloc") {
+            None | Some(JsonValue::Null) => None,
+            Some(alloc) => Some(AllocStats::try_from(alloc)?),
+        };
+
+        let perf = match json.get("perf") {
+            None | Some(JsonValue::Null) => None,
+            Some(perf) => Some(PerfStats::try_from(perf)?),
+        };
+
+        Ok(Stats {
+            cold: duration("cold")?,
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+            min: duration("min")?,
+            median: duration("median")?,
+            mean: duration("mean")?,
+            p95: duration("p95")?,
+            max: duration("max")?,
+            std_dev: duration("std_dev")?,
+            mad: duration("mad")?,
+            alloc,
+            perf,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Linearly interpolated percentile of sorted values.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+fn median_absolute_deviation(sorted: &[f64], median: f64) -> f64 {
+    let mut deviations: Vec<f64> = sorted.iter().map(|x| (x - median).abs()).collect();
+    deviations.sort_unstable_by(f64::total_cmp);
+    percentile(&deviations, 50.0)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn from_nanos(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Stats;
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn summarises_samples() {
+        let stats = Stats::from_samples(
+            Duration::from_nanos(500),
+            &nanos(&[10, 12, 11, 13, 9, 10, 11, 12, 10, 11]),
+        );
+
+        assert_eq!(stats.cold, Duration::from_nanos(500));
+        assert_eq!(stats.samples, 10);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.min, Duration::from_nanos(9));
+        assert_eq!(stats.median, Duration::from_nanos(11));
+        assert_eq!(stats.max, Duration::from_nanos(13));
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_samples(
+            Duration::ZERO,
+            &nanos(&[10, 12, 11, 13, 9, 10, 11, 12, 10, 900]),
+        );
+
+        assert_eq!(stats.samples, 10);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max, Duration::from_nanos(13));
+    }
+
+    #[test]
+    fn handles_identical_samples() {
+        let stats = Stats::from_samples(Duration::ZERO, &nanos(&[7, 7, 7]));
+
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.median, Duration::from_nanos(7));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+    }
+}
@@ -0,0 +1,561 @@
+/// A local log of submitted answers and their verdicts, kept in `data/submissions.json`. It is used
+/// to guard against submissions that are known to be wrong or that would run into a cooldown.
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    fs,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{aoc_cli, Day};
+
+static SUBMISSIONS_FILE_PATH: &str = "./data/submissions.json";
+
+/// How long to wait after a wrong answer if the response does not say.
+const DEFAULT_COOLDOWN_SECS: u64 = 60;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited,
+    /// A response that was not recognized, e.g. for a part that was already solved.
+    Unknown,
+}
+
+impl Verdict {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited => "rate_limited",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    /// Reads the verdict from the response to a submission, together with the number of seconds
+    /// to wait before submitting again, if any.
+    pub fn from_response(response: &str) -> (Self, Option<u64>) {
+        let verdict = if response.contains("That's the right answer") {
+            Verdict::Correct
+        } else if response.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if response.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if response.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if response.contains("You gave an answer too recently") {
+            Verdict::RateLimited
+        } else {
+            Verdict::Unknown
+        };
+
+        let wait = match verdict {
+            Verdict::Correct | Verdict::Unknown => None,
+            Verdict::RateLimited => parse_time_left(response),
+            _ => Some(parse_wait(response).unwrap_or(DEFAULT_COOLDOWN_SECS)),
+        };
+
+        (verdict, wait)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Verdict::Correct,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::Wrong,
+            Verdict::RateLimited,
+            Verdict::Unknown,
+        ]
+        .into_iter()
+        .find(|verdict| verdict.as_str() == s)
+        .ok_or(format!("unknown verdict: {s}"))
+    }
+}
+
+/// Parses e.g. "Please wait one minute before trying again." or "please wait 5 minutes [...]".
+fn parse_wait(response: &str) -> Option<u64> {
+    let (_, rest) = response.split_once("wait ")?;
+    let mut words = rest.split_whitespace();
+
+    let amount = match words.next()? {
+        "one" | "a" => 1,
+        amount => amount.parse().ok()?,
+    };
+
+    match words.next()? {
+        unit if unit.starts_with("second") => Some(amount),
+        unit if unit.starts_with("minute") => Some(amount * 60),
+        unit if unit.starts_with("hour") => Some(amount * 3600),
+        _ => None,
+    }
+}
+
+/// Parses e.g. "You have 1m 38s left to wait."
+fn parse_time_left(response: &str) -> Option<u64> {
+    let (_, rest) = response.split_once("You have ")?;
+    let (left, _) = rest.split_once(" left to wait")?;
+
+    left.split_whitespace()
+        .map(|part| {
+            let (amount, unit) = part.split_at(part.len().checked_sub(1)?);
+            let amount: u64 = amount.parse().ok()?;
+
+            match unit {
+                "s" => Some(amount),
+                "m" => Some(amount * 60),
+                "h" => Some(amount * 3600),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+/// Whether an answer is obviously not meant to be submitted, e.g. the default of a sum or the
+/// starting value of a search for a minimum.
+pub fn is_placeholder(answer: &str) -> bool {
+    let answer = answer.trim();
+
+    let extremes = [
+        i32::MIN.to_string(),
+        i32::MAX.to_string(),
+        u32::MAX.to_string(),
+        i64::MIN.to_string(),
+        i64::MAX.to_string(),
+        u64::MAX.to_string(),
+    ];
+
+    answer.is_empty()
+        || ["0", "-1", "todo", "unimplemented"].contains(&answer.to_lowercase().as_str())
+        || extremes.iter().any(|extreme| extreme == answer)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// When the answer was submitted, in seconds since the unix epoch.
+    pub at: u64,
+    /// When the next answer may be submitted, in seconds since the unix epoch.
+    pub retry_at: Option<u64>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Rehydrate submissions from a JSON file. If not present, returns an empty log.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(SUBMISSIONS_FILE_PATH) {
+            Ok(contents) => Submissions::try_from(contents),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self) -> Result<(), String> {
+        let json = JsonValue::from(self)
+            .format()
+            .map_err(|err| err.to_string())?;
+
+        fs::write(SUBMISSIONS_FILE_PATH, json + "\n").map_err(|err| err.to_string())
+    }
+
+    /// Checks whether `answer` may be submitted at `now`, returning the reason if it may not.
+    pub fn check(&self, day: Day, part: u8, answer: &str, now: u64) -> Result<(), String> {
+        if is_placeholder(answer) {
+            return Err("it looks like a placeholder.".into());
+        }
+
+        let submissions: Vec<&Submission> = self
+            .data
+            .iter()
+            .filter(|submission| submission.day == day && submission.part == part)
+            .collect();
+
+        if let Some(correct) = submissions
+            .iter()
+            .find(|submission| submission.verdict == Verdict::Correct)
+        {
+            return Err(format!(
+                "part {part} was already solved with {}.",
+                correct.answer
+            ));
+        }
+
+        if let Some(known) = submissions.iter().find(|submission| {
+            submission.answer == answer
+                && matches!(
+                    submission.verdict,
+                    Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong
+                )
+        }) {
+            return Err(format!(
+                "it was submitted before and was {}.",
+                known.verdict.as_str().replace('_', " ")
+            ));
+        }
+
+        if let Ok(value) = answer.trim().parse::<i128>() {
+            let bound = |verdict| {
+                submissions
+                    .iter()
+                    .filter(move |submission| submission.verdict == verdict)
+                    .filter_map(|submission| submission.answer.trim().parse::<i128>().ok())
+            };
+
+            if let Some(high) = bound(Verdict::TooHigh).min().filter(|high| value >= *high) {
+                return Err(format!("{high} was too high, so the answer must be lower."));
+            }
+
+            if let Some(low) = bound(Verdict::TooLow).max().filter(|low| value <= *low) {
+                return Err(format!("{low} was too low, so the answer must be higher."));
+            }
+        }
+
+        if let Some(retry_at) = submissions
+            .iter()
+            .filter_map(|submission| submission.retry_at)
+            .max()
+            .filter(|retry_at| *retry_at > now)
+        {
+            return Err(format!(
+                "the cooldown ends in {}s.",
+                retry_at.saturating_sub(now)
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Submits `answer` through aoc-cli and logs the verdict. Returns the logged submission and the
+    /// response of aoc-cli.
+    pub fn submit(
+        &mut self,
+        day: Day,
+        part: u8,
+        answer: &str,
+        now: u64,
+    ) -> Result<(Submission, String), String> {
+        self.submit_with(aoc_cli::PROGRAM, day, part, answer, now)
+    }
+
+    /// Submits `answer` through the aoc-cli binary `program`.
+    fn submit_with(
+        &mut self,
+        program: impl AsRef<OsStr>,
+        day: Day,
+        part: u8,
+        answer: &str,
+        now: u64,
+    ) -> Result<(Submission, String), String> {
+        let output = match aoc_cli::submit(program, day, part, answer) {
+            Ok(output) | Err(aoc_cli::AocCommandError::BadExitStatus(output)) => output,
+            Err(err) => return Err(err.to_string()),
+        };
+
+        let response = String::from_utf8_lossy(&output.stdout).into_owned();
+        let (verdict, wait) = Verdict::from_response(&response);
+
+        let submission = Submission {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            at: now,
+            retry_at: wait.map(|wait| now + wait),
+        };
+
+        self.data.push(submission.clone());
+
+        Ok((submission, response))
+    }
+}
+
+/// The current time in seconds since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submissions> for JsonValue {
+    fn from(value: &Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+        map.insert("at".into(), JsonValue::Number(value.at as f64));
+        map.insert(
+            "retry_at".into(),
+            match value.retry_at {
+                Some(retry_at) => JsonValue::Number(retry_at as f64),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected submission.{key} to be a number."))
+        };
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        let retry_at = match json.get("retry_at") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::Number(retry_at)) => Some(*retry_at as u64),
+            _ => return Err("Expected submission.retry_at to be null or a number.".into()),
+        };
+
+        Ok(Submission {
+            day,
+            part: number("part")? as u8,
+            answer: answer.clone(),
+            verdict,
+            at: number("at")? as u64,
+            retry_at,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_placeholder, Submission, Submissions, Verdict};
+    use crate::day;
+
+    fn submission(answer: &str, verdict: Verdict, retry_at: Option<u64>) -> Submission {
+        Submission {
+            day: day!(4),
+            part: 1,
+            answer: answer.into(),
+            verdict,
+            at: 1_000,
+            retry_at,
+        }
+    }
+
+    #[test]
+    fn reads_verdicts() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            (Verdict::Correct, None)
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too high. If you're stuck, [...] Please wait one minute before trying again."),
+            (Verdict::TooHigh, Some(60))
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too low. [...] please wait 5 minutes before trying again."),
+            (Verdict::TooLow, Some(300))
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer. If you're stuck, [...]"),
+            (Verdict::Wrong, Some(60))
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 38s left to wait."),
+            (Verdict::RateLimited, Some(98))
+        );
+        assert_eq!(
+            Verdict::from_response(
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            (Verdict::Unknown, None)
+        );
+    }
+
+    #[test]
+    fn detects_placeholders() {
+        assert!(is_placeholder(""));
+        assert!(is_placeholder("0"));
+        assert!(is_placeholder("-1"));
+        assert!(is_placeholder("4294967295"));
+        assert!(is_placeholder("18446744073709551615"));
+        assert!(is_placeholder("TODO"));
+        assert!(!is_placeholder("2514"));
+        assert!(!is_placeholder("ABCDEFGH"));
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let log = Submissions {
+            data: vec![submission("1234", Verdict::Wrong, Some(1_060))],
+        };
+
+        assert!(log.check(day!(4), 1, "1234", 2_000).is_err());
+        assert!(log.check(day!(4), 1, "1235", 2_000).is_ok());
+        assert!(log.check(day!(4), 2, "1234", 2_000).is_ok());
+    }
+
+    #[test]
+    fn respects_bounds() {
+        let log = Submissions {
+            data: vec![
+                submission("2000", Verdict::TooHigh, None),
+                submission("1000", Verdict::TooLow, None),
+            ],
+        };
+
+        assert!(log.check(day!(4), 1, "2000", 2_000).is_err());
+        assert!(log.check(day!(4), 1, "2500", 2_000).is_err());
+        assert!(log.check(day!(4), 1, "999", 2_000).is_err());
+        assert!(log.check(day!(4), 1, "1500", 2_000).is_ok());
+    }
+
+    #[test]
+    fn respects_cooldown() {
+        let log = Submissions {
+            data: vec![submission("1234", Verdict::RateLimited, Some(1_060))],
+        };
+
+        assert_eq!(
+            log.check(day!(4), 1, "1500", 1_030),
+            Err("the cooldown ends in 30s.".into())
+        );
+        assert!(log.check(day!(4), 1, "1500", 1_060).is_ok());
+        // a rate limited answer was not checked, and may be submitted again.
+        assert!(log.check(day!(4), 1, "1234", 1_060).is_ok());
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let log = Submissions {
+            data: vec![submission("1500", Verdict::Correct, None)],
+        };
+
+        assert!(log.check(day!(4), 1, "1500", 2_000).is_err());
+    }
+
+    #[test]
+    fn round_trips_submissions() {
+        let log = Submissions {
+            data: vec![
+                submission("1234", Verdict::TooHigh, Some(1_060)),
+                submission("ABC", Verdict::Correct, None),
+            ],
+        };
+
+        let json = tinyjson::JsonValue::from(&log).stringify().unwrap();
+        assert_eq!(Submissions::try_from(json), Ok(log));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn submits_through_fake_aoc_cli() {
+        use std::{env, fs, os::unix::fs::PermissionsExt};
+
+        let script = env::temp_dir().join(format!("fake-aoc-{}", std::process::id()));
+        fs::write(
+            &script,
+            "#!/bin/sh\necho \"That's not the right answer; your answer is too high. Please wait one minute before trying again.\"\n",
+        )
+        .unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut log = Submissions::default();
+        let submitted = log.submit_with(&script, day!(4), 1, "2000", 1_000);
+
+        fs::remove_file(&script).unwrap();
+
+        let (submission, response) = submitted.unwrap();
+        assert!(response.contains("too high"));
+        assert_eq!(submission.verdict, Verdict::TooHigh);
+        assert_eq!(submission.retry_at, Some(1_060));
+        assert_eq!(log.data, vec![submission]);
+
+        assert!(log.check(day!(4), 1, "1500", 1_030).is_err());
+        assert!(log.check(day!(4), 1, "1500", 1_060).is_ok());
+        assert!(log.check(day!(4), 1, "2100", 1_060).is_err());
+    }
+}
@@ -1,7 +1,11 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    alloc_stats::AllocStats,
+    report::{Status, Step},
+    Day,
+};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
@@ -9,8 +13,16 @@ static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Allocations of each step, when counted with the `alloc-stats` feature.
+    pub parse_alloc: Option<AllocStats>,
+    pub part_1_alloc: Option<AllocStats>,
+    pub part_2_alloc: Option<AllocStats>,
+    /// Steps that did not complete and how they stopped, e.g. by panicking, so that they are not
+    /// mistaken for unsolved parts.
+    pub stopped: Vec<(Step, Status)>,
     pub total_nanos: f64,
 }
 
@@ -51,7 +63,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -114,9 +126,18 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        let parse = value.parse.clone().map(JsonValue::String);
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
+        map.insert(
+            "parse".into(),
+            match parse {
+                Some(x) => x,
+                None => JsonValue::Null,
+            },
+        );
+
         map.insert(
             "part_1".into(),
             match part_1 {
@@ -133,6 +154,29 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        let allocs = [
+            ("parse_alloc", &value.parse_alloc),
+            ("part_1_alloc", &value.part_1_alloc),
+            ("part_2_alloc", &value.part_2_alloc),
+        ];
+
+        // only stored when allocations were counted, to keep the file unchanged otherwise.
+        for (key, alloc) in allocs {
+            if let Some(alloc) = alloc {
+                map.insert(key.into(), JsonValue::from(alloc));
+            }
+        }
+
+        if !value.stopped.is_empty() {
+            let stopped = value
+                .stopped
+                .iter()
+                .map(|(step, status)| (step_key(*step), JsonValue::String(status.as_str().into())))
+                .collect();
+
+            map.insert("stopped".into(), JsonValue::Object(stopped));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -151,6 +195,16 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        // timings stored before parsing was timed separately do not have this key.
+        let parse = match json.get("parse") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be null or string.")?,
+            ),
+        };
+
         let part_1 = json
             .get("part_1")
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
@@ -161,6 +215,35 @@ impl TryFrom<&JsonValue> for Timing {
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
             .ok_or("Expected timing.part_2 to be null or string.")?;
 
+        let alloc = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(alloc) => AllocStats::try_from(alloc).map(Some),
+        };
+
+        let stopped = match json.get("stopped") {
+            None => vec![],
+            Some(JsonValue::Object(steps)) => {
+                let mut stopped = steps
+                    .iter()
+                    .map(|(key, status)| {
+                        let step = [Step::Parse, Step::Part(1), Step::Part(2)]
+                            .into_iter()
+                            .find(|step| step_key(*step) == *key);
+                        let status = status.get::<String>().map(|status| status.parse());
+
+                        match (step, status) {
+                            (Some(step), Some(Ok(status))) => Ok((step, status)),
+                            _ => Err(format!("Expected timing.stopped.{key} to be a status.")),
+                        }
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                stopped.sort_unstable_by_key(|(step, _)| step_key(*step));
+                stopped
+            }
+            Some(_) => return Err("Expected timing.stopped to be an object.".into()),
+        };
+
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
@@ -168,13 +251,26 @@ impl TryFrom<&JsonValue> for Timing {
 
         Ok(Timing {
             day,
+            parse: parse.cloned(),
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse_alloc: alloc("parse_alloc")?,
+            part_1_alloc: alloc("part_1_alloc")?,
+            part_2_alloc: alloc("part_2_alloc")?,
+            stopped,
             total_nanos,
         })
     }
 }
 
+/// The key of a step in a timing, e.g. `part_1`.
+fn step_key(step: Step) -> String {
+    match step {
+        Step::Parse => "parse".into(),
+        Step::Part(part) => format!("part_{part}"),
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
@@ -188,20 +284,35 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
+                    stopped: vec![],
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
+                    stopped: vec![],
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
+                    stopped: vec![],
                     total_nanos: 4e+10,
                 },
             ],
@@ -209,7 +320,14 @@ mod tests {
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::{
+                alloc_stats::AllocStats,
+                report::{Status, Step},
+                timings::Timings,
+            },
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -223,6 +341,49 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_parse_timings() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "5µs", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse, Some("5µs".to_string()));
+            assert_eq!(timing.part_1, Some("1ms".to_string()));
+        }
+
+        #[test]
+        fn handles_alloc_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "part_1_alloc": { "bytes": 2048, "count": 3, "peak": 1024 }, "total_nanos": 1000000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse_alloc, None);
+            assert_eq!(
+                timing.part_1_alloc,
+                Some(AllocStats {
+                    bytes: 2048,
+                    count: 3,
+                    peak: 1024
+                })
+            );
+        }
+
+        #[test]
+        fn handles_stopped_steps() {
+            let json = r#"{ "data": [{ "day": "01", "parse": null, "part_1": null, "part_2": null, "stopped": { "part_2": "timed_out", "parse": "panicked" }, "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let stopped = vec![
+                (Step::Parse, Status::Panicked),
+                (Step::Part(2), Status::TimedOut),
+            ];
+            assert_eq!(timings.data[0].part_2, None);
+            assert_eq!(timings.data[0].stopped, stopped);
+
+            let stored = tinyjson::JsonValue::from(timings).stringify().unwrap();
+            assert_eq!(Timings::try_from(stored).unwrap().data[0].stopped, stopped);
+
+            let json = r#"{ "data": [{ "day": "01", "part_1": null, "part_2": null, "stopped": { "part_1": "lost" }, "total_nanos": 0 }] }"#.to_string();
+            assert!(Timings::try_from(json).is_err());
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -279,13 +440,18 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
+                    stopped: vec![],
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +459,18 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
+                    stopped: vec![],
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,13 +478,18 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
+                    stopped: vec![],
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -331,8 +507,13 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
+                    stopped: vec![],
                     total_nanos: 0_f64,
                 }],
             };
@@ -351,8 +532,13 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
+                    stopped: vec![],
                     total_nanos: 0_f64,
                 }],
             };
@@ -0,0 +1,87 @@
+/// Debug output for solutions, printed to stderr by `aoc_debug!` when a solution is run with `-v`
+/// and by `aoc_trace!` with `-vv`. Output is silenced while benching, and compiled out of builds
+/// without debug assertions, such as the release builds that `cargo time` benches.
+use std::{
+    env,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
+
+pub const DEBUG: u8 = 1;
+pub const TRACE: u8 = 2;
+
+static SILENCED: AtomicBool = AtomicBool::new(false);
+
+/// Whether output at `level` is printed.
+pub fn enabled(level: u8) -> bool {
+    static VERBOSITY: OnceLock<u8> = OnceLock::new();
+
+    let verbosity = *VERBOSITY.get_or_init(|| from_args(&env::args().collect::<Vec<_>>()));
+    verbosity >= level && !SILENCED.load(Ordering::Relaxed)
+}
+
+fn from_args(args: &[String]) -> u8 {
+    if args.iter().any(|arg| arg == "-vv") {
+        TRACE
+    } else if args.iter().any(|arg| arg == "-v") {
+        DEBUG
+    } else {
+        0
+    }
+}
+
+/// Runs `func` without any debug output.
+pub(crate) fn silenced<T>(func: impl FnOnce() -> T) -> T {
+    struct Reset;
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            SILENCED.store(false, Ordering::Relaxed);
+        }
+    }
+
+    let _reset = Reset;
+    SILENCED.store(true, Ordering::Relaxed);
+
+    func()
+}
+
+/// Prints a line of debug output to stderr when a solution is run with `-v` or `-vv`.
+///
+/// ```ignore
+/// advent_of_code::aoc_debug!("unexpected token {c} in state {token:?}");
+/// ```
+#[macro_export]
+macro_rules! aoc_debug {
+    ($($arg:tt)*) => {
+        if cfg!(debug_assertions) && $crate::template::verbosity::enabled($crate::template::verbosity::DEBUG) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Prints a line of debug output to stderr when a solution is run with `-vv`.
+#[macro_export]
+macro_rules! aoc_trace {
+    ($($arg:tt)*) => {
+        if cfg!(debug_assertions) && $crate::template::verbosity::enabled($crate::template::verbosity::TRACE) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{from_args, DEBUG, TRACE};
+
+    #[test]
+    fn reads_verbosity() {
+        let args = |args: &[&str]| args.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        assert_eq!(from_args(&args(&["01", "--json"])), 0);
+        assert_eq!(from_args(&args(&["01", "-v"])), DEBUG);
+        assert_eq!(from_args(&args(&["01", "-vv", "--json"])), TRACE);
+    }
+}
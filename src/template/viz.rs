@@ -0,0 +1,117 @@
+/// Hooks for solutions to show their progress while solving, e.g. `viz::frame(&grid)`. They only
+/// draw with the `viz` feature and when the binary is invoked with `--visualize`, and only on the
+/// first run of a part, never while benching. Without the feature, every hook compiles to nothing.
+///
+/// Frames are drawn to stderr, so they never mix with `--json` reports.
+pub use sink::{enabled, frame, log};
+
+pub(crate) use sink::show;
+
+#[cfg(feature = "viz")]
+mod sink {
+    use std::{
+        env,
+        fmt::Display,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            OnceLock,
+        },
+        thread,
+        time::Duration,
+    };
+
+    use crate::template::bench_options::parse_duration;
+
+    const DEFAULT_DELAY: Duration = Duration::from_millis(50);
+    const DELAY_VAR: &str = "AOC_VIZ_DELAY";
+
+    static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+    /// Whether frames are drawn right now. Check this before building a frame that is costly to
+    /// render.
+    pub fn enabled() -> bool {
+        ACTIVE.load(Ordering::Relaxed)
+    }
+
+    /// Clears the terminal and draws a frame, then waits for the frame delay.
+    pub fn frame(frame: impl Display) {
+        if enabled() {
+            eprintln!("\x1b[2J\x1b[H{frame}");
+            thread::sleep(delay());
+        }
+    }
+
+    /// Prints a line of text below the last frame.
+    pub fn log(line: impl Display) {
+        if enabled() {
+            eprintln!("{line}");
+        }
+    }
+
+    /// Runs `func` with the hooks enabled, if the binary was invoked with `--visualize`.
+    pub fn show<T>(func: impl FnOnce() -> T) -> T {
+        struct Reset;
+
+        impl Drop for Reset {
+            fn drop(&mut self) {
+                ACTIVE.store(false, Ordering::Relaxed);
+            }
+        }
+
+        let _reset = Reset;
+        ACTIVE.store(env::args().any(|x| x == "--visualize"), Ordering::Relaxed);
+
+        func()
+    }
+
+    /// Set through the `--viz-delay` option or the `AOC_VIZ_DELAY` env var.
+    fn delay() -> Duration {
+        static DELAY: OnceLock<Duration> = OnceLock::new();
+
+        *DELAY.get_or_init(|| {
+            let args: Vec<String> = env::args().collect();
+            let value = args
+                .iter()
+                .position(|arg| arg == "--viz-delay")
+                .and_then(|index| args.get(index + 1).cloned())
+                .or(env::var(DELAY_VAR).ok());
+
+            match value.as_deref().map(parse_duration) {
+                Some(Ok(delay)) => delay,
+                Some(Err(err)) => {
+                    eprintln!("Invalid frame delay: {err}");
+                    std::process::exit(1);
+                }
+                None => DEFAULT_DELAY,
+            }
+        })
+    }
+}
+
+#[cfg(not(feature = "viz"))]
+mod sink {
+    use std::{env, fmt::Display, sync::Once};
+
+    #[inline(always)]
+    pub const fn enabled() -> bool {
+        false
+    }
+
+    #[inline(always)]
+    pub fn frame(_frame: impl Display) {}
+
+    #[inline(always)]
+    pub fn log(_line: impl Display) {}
+
+    pub fn show<T>(func: impl FnOnce() -> T) -> T {
+        static WARNED: Once = Once::new();
+
+        if env::args().any(|x| x == "--visualize") {
+            WARNED.call_once(|| {
+                eprintln!("--visualize needs the viz feature, e.g. `cargo solve 6 --visualize`.");
+            });
+        }
+
+        func()
+    }
+}